@@ -0,0 +1,292 @@
+/*
+A match server with no graphics. Players connect with the desktop game's `--join address[:port] --room name`
+and are put into rooms by name, each room running its own match. The server owns every match: players only send
+commands, the rules decide whether each one is allowed, and the result is sent back to everyone in the room.
+Everything that happens in a room is written to its own log file. Spectators connect with `--watch address[:port] --room name`
+and are sent the room's match like its players, but have no team and so can never give a command.
+
+Run it with `cargo run --bin elder_server -- [--port 7878] [--logs logs]`.
+*/
+
+use i_am_the_elder_god::game_logic::network::{Link, ClientMessage, HostMessage, DEFAULT_PORT, DEFAULT_ROOM};
+use i_am_the_elder_god::gameplay_logic::ascii_board::team_name;
+use i_am_the_elder_god::gameplay_logic::entities::PlayerType;
+use i_am_the_elder_god::gameplay_logic::game_match::{Match, MatchEvent, Command};
+use i_am_the_elder_god::gameplay_logic::match_config::MatchConfig;
+
+use quicksilver::prelude::*;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long the server sleeps between checking its connections
+const TICK: Duration = Duration::from_millis(10);
+/// Longest room name kept, the name is also the log's file name
+const MAX_ROOM_NAME: usize = 32;
+
+/// Returns the other team
+fn other_team(team: PlayerType) -> PlayerType {
+    if team == PlayerType::Player1 { PlayerType::Player2 } else { PlayerType::Player1 }
+}
+
+/// Keeps the letters, digits, dashes and underscores of a room name so it is safe as a file name
+fn room_name(name: &str) -> String {
+    let name: String = name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_').take(MAX_ROOM_NAME).collect();
+    if name.is_empty() { DEFAULT_ROOM.to_string() } else { name }
+}
+
+/// Someone connected to the server, in a room once they have joined one
+struct Player {
+    id: u64, //Counts up from 1 so players can be told apart in logs
+    link: Link,
+    room: Option<String>,
+    team: Option<PlayerType>,
+    rematch: bool, //Asked to play again after the room's match was won
+    gone: bool, //Dropped, removed at the end of the poll
+}
+
+/// A match and the log of everything that happened in it
+struct Room {
+    game: Match,
+    log: File,
+    opened: Instant,
+}
+
+impl Room {
+    /// Starts a room with a match set up from `config`, appending to its log if one is already there
+    fn open(name: &str, config: MatchConfig, log_dir: &Path) -> Result<Self> {
+        let log = OpenOptions::new().create(true).append(true).open(log_dir.join(format!("{}.log", name)))?;
+        let mut room = Self { game: Match::new(config)?, log, opened: Instant::now() };
+        room.log(&format!("Room {} opened, {:?}", name, config))?;
+        Ok(room)
+    }
+
+    /// Writes a line to the room's log, stamped with the time since the room opened
+    fn log(&mut self, line: &str) -> Result<()> {
+        writeln!(self.log, "[{:>9.3}s] {}", self.opened.elapsed().as_secs_f64(), line)?;
+        Ok(())
+    }
+}
+
+/// Runs every room and carries messages between rooms and their players
+struct Server {
+    listener: TcpListener,
+    log_dir: PathBuf,
+    players: Vec<Player>,
+    rooms: HashMap<String, Room>,
+    next_id: u64,
+}
+
+impl Server {
+    /// Starts listening for players on every interface, logs are written into `log_dir`
+    fn bind(port: u16, log_dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&log_dir)?;
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, log_dir, players: vec![], rooms: HashMap::new(), next_id: 1 })
+    }
+
+    /// Accepts new players, carries out what every player has sent and forgets players that dropped
+    /// A player whose message fails in their room is dropped, the other rooms carry on
+    fn poll(&mut self) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if let Ok(link) = Link::new(stream) {
+                self.players.push(Player { id: self.next_id, link, room: None, team: None, rematch: false, gone: false });
+                self.next_id += 1;
+            }
+        }
+
+        for index in 0..self.players.len() {
+            match self.players[index].link.receive::<ClientMessage>() {
+                Ok(messages) => for message in messages {
+                    let handled = match message {
+                        ClientMessage::Join { room, team, config } => self.join(index, &room_name(&room), team, config),
+                        ClientMessage::Watch { room }              => self.watch(index, &room_name(&room)),
+                        ClientMessage::Command(command)            => self.command(index, command),
+                    };
+                    if let Err(err) = handled {
+                        eprintln!("Dropping player {}: {}", self.players[index].id, err);
+                        self.players[index].gone = true;
+                        break;
+                    }
+                },
+                Err(_) => self.players[index].gone = true,
+            }
+        }
+
+        //Dropped players free their team so they can join again, finished rooms nobody is in are closed
+        for index in 0..self.players.len() {
+            if !self.players[index].gone { continue; }
+            if let Err(err) = self.leave(index) {
+                eprintln!("Cannot log player {} leaving: {}", self.players[index].id, err);
+            }
+        }
+        self.players.retain(|player| !player.gone);
+        let players = &self.players;
+        self.rooms.retain(|name, room| {
+            room.game.winner() == PlayerType::Undetermined || players.iter().any(|player| player.room.as_deref() == Some(name))
+        });
+    }
+
+    /// Puts a player into a room, starting the room if it is new
+    /// A player already in the room is sent the match again, or asks for a rematch once it has been won
+    fn join(&mut self, index: usize, name: &str, wanted: PlayerType, mut config: MatchConfig) -> Result<()> {
+        if self.players[index].room.as_deref() != Some(name) { self.leave(index)?; }
+        if !self.rooms.contains_key(name) {
+            //The config comes from the client, a room is never opened with a match that cannot be set up
+            if let Err(err) = config.check() {
+                let refused = HostMessage::Refused(format!("Cannot open room {}: {}", name, err));
+                if self.players[index].link.send(&refused).is_err() { self.players[index].gone = true; }
+                return Ok(());
+            }
+            config.seed = rand::random(); //The server rolls the seed so no client picks its own damage rolls
+            let room = Room::open(name, config, &self.log_dir)?;
+            self.rooms.insert(name.to_string(), room);
+        }
+
+        let id = self.players[index].id;
+        let room = self.rooms.get_mut(name).expect("Room was just opened");
+        let team = match self.players[index].team {
+            Some(team) => {
+                if room.game.winner() != PlayerType::Undetermined { self.players[index].rematch = true; }
+                team
+            },
+            None => {
+                let taken: Vec<PlayerType> = self.players.iter().filter(|player| player.room.as_deref() == Some(name))
+                    .filter_map(|player| player.team).collect();
+                let team = if !taken.contains(&wanted) { wanted } else { other_team(wanted) };
+                if taken.contains(&team) {
+                    room.log(&format!("Player {} refused, the room is full", id))?;
+                    let refused = HostMessage::Refused(format!("Room {} is full", name));
+                    if self.players[index].link.send(&refused).is_err() { self.players[index].gone = true; }
+                    return Ok(());
+                }
+                room.log(&format!("Player {} joined as the {}", id, team_name(team)))?;
+                let player = &mut self.players[index];
+                player.room = Some(name.to_string());
+                player.team = Some(team);
+                player.rematch = false;
+                team
+            },
+        };
+
+        //Everyone playing in a won room asking again starts the next match with a new environment
+        let in_room = self.players.iter().filter(|player| player.room.as_deref() == Some(name) && player.team.is_some());
+        if room.game.winner() != PlayerType::Undetermined && in_room.clone().all(|player| player.rematch) {
+            let mut config = *room.game.config();
+            config.seed = rand::random();
+            room.game = Match::new(config)?;
+            room.log("Rematch started")?;
+            for player in self.players.iter_mut().filter(|player| player.room.as_deref() == Some(name)) { player.rematch = false; }
+            return self.broadcast(name, &[]);
+        }
+
+        let player = &mut self.players[index];
+        let sent = player.link.send(&HostMessage::Welcome(team)).is_ok() &&
+            player.link.send(&HostMessage::state(&room.game, Some(team), &[])?).is_ok();
+        if !sent { player.gone = true; }
+        Ok(())
+    }
+
+    /// Puts a spectator into a room that is already running and sends them its match
+    fn watch(&mut self, index: usize, name: &str) -> Result<()> {
+        if !self.rooms.contains_key(name) {
+            let refused = HostMessage::Refused(format!("No match in room {}", name));
+            if self.players[index].link.send(&refused).is_err() { self.players[index].gone = true; }
+            return Ok(());
+        }
+        let arriving = self.players[index].room.as_deref() != Some(name);
+        if arriving { self.leave(index)?; }
+
+        let player = &mut self.players[index];
+        let room = self.rooms.get_mut(name).expect("Room was just checked");
+        if arriving {
+            room.log(&format!("Spectator {} watching", player.id))?;
+            player.room = Some(name.to_string());
+        }
+        if player.link.send(&HostMessage::state(&room.game, None, &[])?).is_err() { player.gone = true; }
+        Ok(())
+    }
+
+    /// Carries out a player's command if it is theirs to give and the rules allow it, then sends the room the result
+    /// Players only play their own team, and only on its turn unless they are conceding
+    fn command(&mut self, index: usize, command: Command) -> Result<()> {
+        let player = &self.players[index];
+        let (name, team) = match (player.room.clone(), player.team) {
+            (Some(name), Some(team)) => (name, team),
+            _                        => return Ok(()), //Commands before joining or from spectators are ignored
+        };
+        let id = player.id;
+        let room = self.rooms.get_mut(&name).expect("Players are only in open rooms");
+
+        let allowed = match command {
+            Command::Concede(conceding) => conceding == team,
+            _                           => room.game.team()? == team,
+        };
+        if !allowed {
+            room.log(&format!("Player {} ({}) {:?} refused, not theirs to give", id, team_name(team), command))?;
+            return Ok(());
+        }
+        //The match checks moves with can_move and abilities with can_act before carrying them out
+        if !room.game.apply(command)? {
+            room.log(&format!("Player {} ({}) {:?} refused by the rules", id, team_name(team), command))?;
+            return Ok(());
+        }
+
+        room.log(&format!("Player {} ({}) {:?}", id, team_name(team), command))?;
+        let events = room.game.drain_events();
+        for event in events.iter() {
+            if let MatchEvent::TurnChanged { to, .. } = event {
+                let line = format!("Round {}, the {} to play", room.game.round(), team_name(*to));
+                room.log(&line)?;
+            }
+        }
+        if room.game.winner() != PlayerType::Undetermined {
+            let line = format!("The {} win", team_name(room.game.winner()));
+            room.log(&line)?;
+        }
+        self.broadcast(&name, &events)
+    }
+
+    /// Takes a player or spectator out of their room, logging it
+    fn leave(&mut self, index: usize) -> Result<()> {
+        let player = &mut self.players[index];
+        if let Some(name) = player.room.take() {
+            if let Some(room) = self.rooms.get_mut(&name) {
+                match player.team.take() {
+                    Some(team) => room.log(&format!("Player {} ({}) left", player.id, team_name(team)))?,
+                    None       => room.log(&format!("Spectator {} stopped watching", player.id))?,
+                }
+            }
+        }
+        player.rematch = false;
+        Ok(())
+    }
+
+    /// Sends a room's match and what just happened to everyone in it, spectators included, each as they may see it
+    fn broadcast(&mut self, name: &str, events: &[MatchEvent]) -> Result<()> {
+        let room = self.rooms.get(name).expect("Broadcasting an open room");
+        for player in self.players.iter_mut().filter(|player| player.room.as_deref() == Some(name)) {
+            if player.link.send(&HostMessage::state(&room.game, player.team, events)?).is_err() { player.gone = true; }
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let value = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|index| args.get(index + 1)).cloned();
+    let port = value("--port").and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT);
+    let log_dir = PathBuf::from(value("--logs").unwrap_or_else(|| "logs".to_string()));
+
+    let mut server = Server::bind(port, log_dir.clone()).expect("Cannot start the server, is the port already in use?");
+    println!("Elder God server listening on port {}, room logs in {}", port, log_dir.display());
+
+    loop {
+        server.poll();
+        std::thread::sleep(TICK);
+    }
+}
@@ -0,0 +1,112 @@
+/*
+Plays a match in a terminal with no window, for debugging and over SSH. Both teams share the keyboard and
+every command goes through the same rules as the desktop game. The board is drawn by `ascii_board` after
+each command.
+
+Run it with `cargo run --bin elder_terminal -- [--level 1] [--seed 0]` and type `help` for the commands.
+*/
+
+use i_am_the_elder_god::gameplay_logic::ascii_board::{render_match, team_name};
+use i_am_the_elder_god::gameplay_logic::entities::PlayerType;
+use i_am_the_elder_god::gameplay_logic::game_match::{Match, MatchEvent, Command};
+use i_am_the_elder_god::gameplay_logic::gameplay_type::Direction;
+use i_am_the_elder_god::gameplay_logic::match_config::MatchConfig;
+
+use quicksilver::prelude::*;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  move <up|down|left|right>        Move one cell, `m` and the first letter of a direction also work
+  act <1-3> <up|down|left|right>   Use an ability aimed in a direction, `a` also works
+  end                              End the turn
+  concede                          Give the match to the other team
+  board                            Draw the board again
+  quit                             Leave
+Land: R road, # plain, F forest, A mountain, C city, W wall, O pit, M spikes, X destroyed, - empty
+Conditions: B burning, F frozen, S shielded, - impassable
+Entities: s support, a assault, t trapper, w wraith, k kraken, e elder";
+
+/// Reads a direction by name or first letter
+fn direction(word: &str) -> Option<Direction> {
+    match word {
+        "up" | "u"    => Some(Direction::Up),
+        "down" | "d"  => Some(Direction::Down),
+        "left" | "l"  => Some(Direction::Left),
+        "right" | "r" => Some(Direction::Right),
+        _             => None,
+    }
+}
+
+/// Reads a command typed for the team whose turn it is, None if it is not one
+fn parse(line: &str, team: PlayerType) -> Option<Command> {
+    let words: Vec<String> = line.split_whitespace().map(|word| word.to_lowercase()).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        ["move", dir] | ["m", dir]                  => direction(dir).map(Command::Move),
+        ["act", ability, dir] | ["a", ability, dir] => {
+            let ability = ability.parse().ok().filter(|ability| (1..=3).contains(ability))?;
+            direction(dir).map(|dir| Command::Act(ability, dir))
+        },
+        ["end"] | ["e"]                             => Some(Command::EndTurn),
+        ["concede"]                                 => Some(Command::Concede(team)),
+        _                                           => None,
+    }
+}
+
+/// Describes what a command caused
+fn describe(game: &Match, event: &MatchEvent) -> Result<String> {
+    let class = |entity: usize| -> Result<&str> { Ok(game.entities()[entity].get_class()?.key()) };
+    Ok(match event {
+        MatchEvent::Moved { entity, to, .. }          => format!("{} moved to {},{}", class(*entity)?, to.x, to.y),
+        MatchEvent::Teleported { entity, to, .. }     => format!("{} was moved to {},{}", class(*entity)?, to.x, to.y),
+        MatchEvent::Hazard { entity }                 => format!("{} stepped onto hurtful land", class(*entity)?),
+        MatchEvent::Ability { ability, affected, .. } => format!("{:?} reached {} cells", ability, affected.len()),
+        MatchEvent::TurnChanged { to, .. }            => format!("The turn passes to the {}", team_name(*to)),
+    })
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let value = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|index| args.get(index + 1)).cloned();
+    let config = MatchConfig {
+        level: value("--level").and_then(|level| level.parse().ok()).unwrap_or(1),
+        seed: value("--seed").and_then(|seed| seed.parse().ok()).unwrap_or(0),
+        ..MatchConfig::default()
+    };
+
+    let mut game = Match::new(config)?;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    print!("{}", render_match(&game)?);
+
+    while game.winner() == PlayerType::Undetermined {
+        print!("{} {}> ", team_name(game.team()?), game.current_entity().get_class()?.key());
+        io::stdout().flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None       => break, //Input closed
+        };
+
+        match line.trim() {
+            ""                 => continue,
+            "help" | "h" | "?" => { println!("{}", HELP); continue; },
+            "board" | "b"      => { print!("{}", render_match(&game)?); continue; },
+            "quit" | "q"       => break,
+            _                  => {},
+        }
+        let command = match parse(&line, game.team()?) {
+            Some(command) => command,
+            None          => { println!("Unknown command, type `help` for the list"); continue; },
+        };
+
+        if !game.apply(command)? {
+            println!("{:?} is not allowed", command);
+            continue;
+        }
+        for event in game.drain_events() { println!("{}", describe(&game, &event)?); }
+        print!("{}", render_match(&game)?);
+    }
+
+    Ok(())
+}
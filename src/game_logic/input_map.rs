@@ -0,0 +1,244 @@
+/*
+Maps physical keys to the logical actions scenes respond to. Scenes ask whether an action was
+pressed instead of reading keys directly, so every control can be rebound from the settings scene.
+Bindings are saved with quicksilver's saving feature and fall back to the defaults when missing.
+
+Gamepads use a fixed layout: the D-pad moves and aims, face buttons pick Move/Action/End/Confirm,
+shoulder buttons cycle abilities, Start cancels and Select concedes. Each team can be limited to one
+pad so hotseat players each drive their own side.
+*/
+
+use quicksilver::prelude::*;
+use quicksilver::input::{Keyboard, Gamepad};
+use quicksilver::saving::{save, load};
+use serde::{Serialize, Deserialize};
+
+/// Name the bindings are saved under
+const APP_NAME: &str = "I am the Elder God";
+const PROFILE: &str = "bindings";
+
+/// Keys that can be bound to an action, quicksilver's keys cannot be saved directly so they are stored by name
+const BINDABLE_KEYS: &[Key] = &[
+    Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9, Key::Key0,
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+    Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::Up, Key::Left, Key::Down, Key::Right, Key::Return, Key::Space, Key::Back, Key::Tab, Key::Escape,
+    Key::Numpad0, Key::Numpad1, Key::Numpad2, Key::Numpad3, Key::Numpad4,
+    Key::Numpad5, Key::Numpad6, Key::Numpad7, Key::Numpad8, Key::Numpad9, Key::NumpadEnter,
+    Key::LShift, Key::RShift, Key::LControl, Key::RControl,
+    Key::Comma, Key::Period, Key::Slash, Key::Semicolon, Key::Minus, Key::Equals,
+];
+
+/// Everything a player can do with a key
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveMode,
+    ActionMode,
+    EndTurn,
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Cancel,
+    Concede,
+}
+
+/// Every action in the order they are listed in settings
+pub const INPUT_ACTIONS: [InputAction; 10] = [
+    InputAction::MoveMode, InputAction::ActionMode, InputAction::EndTurn,
+    InputAction::Up, InputAction::Down, InputAction::Left, InputAction::Right,
+    InputAction::Confirm, InputAction::Cancel, InputAction::Concede,
+];
+
+impl InputAction {
+    /// Returns the key an action is bound to before the player changes it
+    pub fn default_key(&self) -> Key {
+        match self {
+            InputAction::MoveMode   => Key::M,
+            InputAction::ActionMode => Key::A,
+            InputAction::EndTurn    => Key::E,
+            InputAction::Up         => Key::Up,
+            InputAction::Down       => Key::Down,
+            InputAction::Left       => Key::Left,
+            InputAction::Right      => Key::Right,
+            InputAction::Confirm    => Key::Return,
+            InputAction::Cancel     => Key::Escape,
+            InputAction::Concede    => Key::Key0,
+        }
+    }
+
+    /// Returns the gamepad button that triggers an action
+    pub fn pad_button(&self) -> GamepadButton {
+        match self {
+            InputAction::MoveMode   => GamepadButton::FaceLeft,
+            InputAction::ActionMode => GamepadButton::FaceUp,
+            InputAction::EndTurn    => GamepadButton::FaceRight,
+            InputAction::Up         => GamepadButton::DpadUp,
+            InputAction::Down       => GamepadButton::DpadDown,
+            InputAction::Left       => GamepadButton::DpadLeft,
+            InputAction::Right      => GamepadButton::DpadRight,
+            InputAction::Confirm    => GamepadButton::FaceDown,
+            InputAction::Cancel     => GamepadButton::Start,
+            InputAction::Concede    => GamepadButton::Select,
+        }
+    }
+
+    /// Returns the name shown in settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputAction::MoveMode   => "Move",
+            InputAction::ActionMode => "Action",
+            InputAction::EndTurn    => "End",
+            InputAction::Up         => "Up",
+            InputAction::Down       => "Down",
+            InputAction::Left       => "Left",
+            InputAction::Right      => "Right",
+            InputAction::Confirm    => "Confirm",
+            InputAction::Cancel     => "Cancel",
+            InputAction::Concede    => "Concede",
+        }
+    }
+}
+
+/// Returns the short name of a key used in help text, I.E. `Key0` is shown as `0`
+pub fn key_name(key: Key) -> String {
+    match key {
+        Key::Return      => "Enter".to_string(),
+        Key::Escape      => "Esc".to_string(),
+        Key::Back        => "Backspace".to_string(),
+        Key::NumpadEnter => "NumEnter".to_string(),
+        _                => {
+            let name = format!("{:?}", key);
+            if let Some(digit) = name.strip_prefix("Key") { digit.to_string() }
+            else if let Some(digit) = name.strip_prefix("Numpad") { format!("Num{}", digit) }
+            else { name }
+        }
+    }
+}
+
+/// Returns the first bindable key pressed this update, used when rebinding
+pub fn pressed_key(keyboard: &Keyboard) -> Option<Key> {
+    BINDABLE_KEYS.iter().find(|key| keyboard[**key] == ButtonState::Pressed).copied()
+}
+
+/// Which connected gamepads a team listens to, pads are numbered in the order they were connected
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum PadSlot {
+    Any,
+    Slot(usize),
+    Off,
+}
+
+/// The keyboard and the gamepads an update listens to
+pub struct Controls<'a> {
+    keyboard: &'a Keyboard,
+    pads: Vec<&'a Gamepad>,
+}
+
+impl<'a> Controls<'a> {
+    /// Listens to the keyboard and the pads in `slot`
+    pub fn new(window: &'a Window, slot: PadSlot) -> Self {
+        let pads = window.gamepads().iter().enumerate()
+            .filter(|(index, _)| match slot {
+                PadSlot::Any         => true,
+                PadSlot::Slot(wants) => *index == wants,
+                PadSlot::Off         => false,
+            })
+            .map(|(_, pad)| pad)
+            .collect();
+
+        Self { keyboard: window.keyboard(), pads }
+    }
+
+    /// True on the update `button` goes down on any listened pad
+    pub fn button_pressed(&self, button: GamepadButton) -> bool {
+        self.pads.iter().any(|pad| pad[button] == ButtonState::Pressed)
+    }
+}
+
+/// The bindings as they are written to disk
+#[derive(Serialize, Deserialize)]
+struct SavedBindings {
+    bindings: Vec<(InputAction, String)>,
+}
+
+/// One key for every action
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputMap {
+    bindings: Vec<(InputAction, Key)>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self { bindings: INPUT_ACTIONS.iter().map(|action| (*action, action.default_key())).collect() }
+    }
+}
+
+impl InputMap {
+    /// Loads the saved bindings, any action that is missing or saved with an unknown key keeps its default
+    pub fn load() -> Self {
+        let mut input_map = InputMap::default();
+        if let Ok(saved) = load::<SavedBindings>(APP_NAME, PROFILE) {
+            for (action, name) in saved.bindings {
+                if let Some(key) = BINDABLE_KEYS.iter().find(|key| format!("{:?}", key) == name) {
+                    input_map.bind(action, *key);
+                }
+            }
+        }
+        input_map
+    }
+
+    /// Saves the bindings so they are restored next session
+    pub fn save(&self) -> Result<()> {
+        let saved = SavedBindings {
+            bindings: self.bindings.iter().map(|(action, key)| (*action, format!("{:?}", key))).collect(),
+        };
+        save(APP_NAME, PROFILE, &saved)?;
+        Ok(())
+    }
+
+    /// Returns the key bound to an action
+    pub fn key(&self, action: InputAction) -> Key {
+        self.bindings.iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, key)| *key)
+            .expect("Every action has a binding")
+    }
+
+    /// Returns the short name of the key bound to an action
+    pub fn name(&self, action: InputAction) -> String {
+        key_name(self.key(action))
+    }
+
+    /// True on the update the key bound to `action` or its gamepad button goes down
+    pub fn pressed(&self, controls: &Controls, action: InputAction) -> bool {
+        self.key_pressed(controls, action) || controls.button_pressed(action.pad_button())
+    }
+
+    /// True on the update the key bound to `action` goes down, ignoring gamepads
+    pub fn key_pressed(&self, controls: &Controls, action: InputAction) -> bool {
+        controls.keyboard[self.key(action)] == ButtonState::Pressed
+    }
+
+    /// Binds a key to an action. An action that already used the key takes this action's old key,
+    /// so two actions never share a key
+    pub fn bind(&mut self, action: InputAction, key: Key) {
+        let old_key = self.key(action);
+        for (bound, bound_key) in self.bindings.iter_mut() {
+            if *bound == action { *bound_key = key; }
+            else if *bound_key == key { *bound_key = old_key; }
+        }
+    }
+
+    /// Returns the text of a menu word with its key, I.E. `[M]ove` when the key is the word's first letter and `Move [X]` otherwise
+    pub fn menu_label(&self, action: InputAction, word: &str) -> String {
+        let name = self.name(action);
+        match word.chars().next() {
+            Some(first) if name.len() == 1 && name.eq_ignore_ascii_case(&first.to_string()) => {
+                format!("[{}]{}", first, &word[first.len_utf8()..])
+            },
+            _ => format!("{} [{}]", word, name),
+        }
+    }
+}
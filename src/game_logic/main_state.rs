@@ -3,11 +3,18 @@ Here we define the overarching 'Game' which contains all of its sub-components a
 */
 
 use crate::game_logic::scene_type::{SceneType, SceneReturn};
-use crate::game_logic::music_player::MusicPlayer;
+use crate::game_logic::music_player::{MusicPlayer, MusicTrack};
+use crate::game_logic::sound_bank::{SoundBank, VolumeCategory};
+use crate::game_logic::preferences::Preferences;
+use crate::game_logic::input_map::InputMap;
+use crate::gameplay_logic::animator::Clock;
+use crate::gameplay_logic::entities::PlayerType;
 use crate::game_logic::draw_helper::*;
 use crate::scenes::game::ElderGame;
 use crate::scenes::intro::ElderIntro;
+use crate::scenes::setup::ElderSetup;
 use crate::scenes::outro::ElderOutro;
+use crate::scenes::settings::ElderSettings;
 
 //Resources
 use quicksilver::prelude::*;
@@ -15,19 +22,27 @@ use quicksilver::prelude::*;
 use std::vec::IntoIter;
 use std::iter::Cycle;
 
+/// The order scenes are played in, settings are opened separately and return to where they were opened
+const SCENE_ORDER: [SceneType; 4] = [SceneType::Intro, SceneType::Setup, SceneType::Game, SceneType::Outro];
+
 pub struct Game {
     //For scene order control
     curr_scene: SceneType,
     scene_circle_iterator: Cycle<IntoIter<SceneType>>,
+    settings_return: SceneType, //Scene to go back to when settings close
 
     //Scene Data
     intro_scenes: ElderIntro,
+    setup_scene: ElderSetup,
     game_scenes: ElderGame,
     outro_scenes: ElderOutro,
+    settings_scene: ElderSettings,
 
     //Large Files
     overlay: Asset<Image>,
     bg_music: MusicPlayer,
+    sounds: SoundBank,
+    clock: Clock, //Times the music player's updates
 }
 
 impl State for Game {
@@ -36,58 +51,104 @@ impl State for Game {
 
         //Scene resource allocations, this defines states
         let intro = ElderIntro::new().expect("Cannot load Elder Intro");
+        let setup = ElderSetup::new().expect("Cannot load Elder Setup");
         let game = ElderGame::new().expect("Cannot load Elder Game");
         let outro = ElderOutro::new().expect("Cannot load Elder Outro");
+        let settings = ElderSettings::new().expect("Cannot load Elder Settings");
 
         //Large/universal data allocations, waste not want not
-        let music = MusicPlayer::new("vgm21.wav", 19.0, 1.0)
+        let music = MusicPlayer::new(1.0)
             .expect("Cannot initialize MusicPlayer in main_state::new");
+        let sounds = SoundBank::new().expect("Cannot initialize SoundBank in main_state::new");
         let game_overlay = Asset::new(Image::load("PHFrame.png"));
 
         //Scene order allocation, this defines the order of states
-        let scenes: Vec<SceneType> = vec![SceneType::Intro, SceneType::Game, SceneType::Outro];
+        let scenes: Vec<SceneType> = SCENE_ORDER.to_vec();
         let mut scene_cycle: Cycle<IntoIter<SceneType>> = scenes.into_iter().cycle();
         let first_scene: SceneType = scene_cycle.next().expect("Empty scene buffer in Game::new(), cannot continue.");
 
 
-        Ok(Self {
+        let mut elder_game = Self {
             curr_scene: first_scene,
             scene_circle_iterator: scene_cycle,
+            settings_return: first_scene,
 
             intro_scenes: intro,
+            setup_scene: setup,
             game_scenes: game,
             outro_scenes: outro,
+            settings_scene: settings,
 
             //Large Files
             overlay: game_overlay,
             bg_music: music,
-        })
+            sounds,
+            clock: Clock::new(),
+        };
+        //Saved preferences and bindings apply from the start
+        let preferences = elder_game.settings_scene.get_preferences()?;
+        let input = elder_game.settings_scene.get_input_map()?;
+        elder_game.apply_preferences(&preferences, &input)?;
+
+        Ok(elder_game)
     }
 
     /// Process keyboard and mouse, update the game state
     fn update(&mut self, window: &mut Window) -> Result<()> {
         let scene_flag = match self.curr_scene {
-            SceneType::Intro => self.intro_scenes.update(window)?,
+            SceneType::Intro => self.intro_scenes.update(window, &mut self.sounds)?,
+            SceneType::Setup => {
+                let scene_retval = self.setup_scene.update(window, &mut self.sounds)?;
+                if scene_retval == SceneReturn::Finished { //Build the match that was chosen
+                    self.game_scenes.start(self.setup_scene.get_config()?)?;
+                }
+                scene_retval
+            }
             SceneType::Game => {
-                let scene_retval = self.game_scenes.update(window)?;
+                let scene_retval = self.game_scenes.update(window, &mut self.sounds)?;
                 self.outro_scenes.set_winner(self.game_scenes.get_winner()?)?;
-                if scene_retval == SceneReturn::Finished{  //Game is over so we call reset.
+                if scene_retval == SceneReturn::Finished || scene_retval == SceneReturn::Title {  //Game is over so we call reset.
                     self.game_scenes.reset()?;
                 }
                 scene_retval
             }
             SceneType::Outro => {
-                self.outro_scenes.update(window)?
+                self.outro_scenes.update(window, &mut self.sounds)?
             }
+            SceneType::Settings => self.settings_scene.update(window, &mut self.sounds)?,
         };
 
         match scene_flag {
-            SceneReturn::Good => Ok(()), //Do not transition
+            SceneReturn::Good => {}, //Do not transition
+            SceneReturn::Finished if self.curr_scene == SceneType::Settings => { //Go back to where settings were opened
+                let preferences = self.settings_scene.get_preferences()?;
+                let input = self.settings_scene.get_input_map()?;
+                self.apply_preferences(&preferences, &input)?;
+                self.curr_scene = self.settings_return;
+            }
             SceneReturn::Finished => { //Do transition
                 self.curr_scene = self.scene_circle_iterator.next().unwrap();
-                Ok(())
+            }
+            SceneReturn::Settings => {
+                self.settings_return = self.curr_scene;
+                self.curr_scene = SceneType::Settings;
+            }
+            SceneReturn::Title => { //Restart the scene order from the intro
+                let mut scene_cycle = SCENE_ORDER.to_vec().into_iter().cycle();
+                self.curr_scene = scene_cycle.next().expect("Empty scene buffer in Game::update, cannot continue.");
+                self.scene_circle_iterator = scene_cycle;
             }
         }
+
+        //Music follows the scene, the outro plays victory or defeat for the team on this screen
+        let music_scene = if self.curr_scene == SceneType::Settings { self.settings_return } else { self.curr_scene };
+        let tension = music_scene == SceneType::Game && self.game_scenes.is_tense()?;
+        let winner = self.outro_scenes.get_winner()?;
+        let won = winner != PlayerType::Undetermined && winner == self.game_scenes.local_team()?;
+        self.bg_music.select(MusicTrack::for_scene(music_scene, tension, won));
+        self.bg_music.update(self.clock.tick())?;
+
+        Ok(())
     }
 
     /// Handle various sorts of events, https://docs.rs/quicksilver/0.3.16/quicksilver/lifecycle/enum.Event.html
@@ -95,8 +156,10 @@ impl State for Game {
         //Result is passed up
         let retval = match self.curr_scene {
             SceneType::Intro => self.intro_scenes.event(event, window),
+            SceneType::Setup => self.setup_scene.event(event, window),
             SceneType::Game => self.game_scenes.event(event, window),
             SceneType::Outro => self.outro_scenes.event(event, window),
+            SceneType::Settings => self.settings_scene.event(event, window),
         };
 
         retval
@@ -112,8 +175,6 @@ impl State for Game {
     fn draw(&mut self, window: &mut Window) -> Result<()> {
         //window.clear(Color::INDIGO)?;
         let window_center = Vector::new(window.screen_size().x as i32 / 2, window.screen_size().y as i32 / 2);
-        //See music_player.rs for reasoning
-        self.bg_music.play_if_not(window.current_fps())?;
 
         //Draw overlay first to put it on the bottom.
         draw_ex_with_center(window, &mut self.overlay, window_center, Transform::IDENTITY, 0.0)?;
@@ -121,10 +182,31 @@ impl State for Game {
         //Result is passed up
         let retval = match self.curr_scene {
             SceneType::Intro => self.intro_scenes.draw(window),
+            SceneType::Setup => self.setup_scene.draw(window),
             SceneType::Game => self.game_scenes.draw(window),
             SceneType::Outro => self.outro_scenes.draw(window),
+            SceneType::Settings => self.settings_scene.draw(window),
         };
 
         retval
     }
+}
+
+impl Game {
+    /// Hands the player's preferences to the sound bank, music player, setup and game scenes, and their bindings to every scene
+    fn apply_preferences(&mut self, preferences: &Preferences, input: &InputMap) -> Result<()> {
+        //Interface sounds follow the effects volume
+        self.sounds.set_volume(VolumeCategory::Sfx, preferences.sfx_volume);
+        self.sounds.set_volume(VolumeCategory::Ui, preferences.sfx_volume);
+        self.bg_music.set_volume(preferences.music_volume);
+        self.bg_music.set_muted(preferences.music_muted);
+        self.game_scenes.set_preferences(preferences)?;
+        self.setup_scene.set_preferences(preferences)?;
+
+        self.intro_scenes.set_input_map(input)?;
+        self.setup_scene.set_input_map(input)?;
+        self.game_scenes.set_input_map(input)?;
+        self.outro_scenes.set_input_map(input)?;
+        Ok(())
+    }
 }
\ No newline at end of file
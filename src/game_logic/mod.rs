@@ -10,7 +10,17 @@ level.
 pub mod main_state;
 /// Contains information about Scenes and their types
 pub mod scene_type;
-/// Plays music with a system dependant on the fps in draw calls
+/// Plays background music that follows the scene
 pub mod music_player;
+/// Plays sound effects keyed by game events
+pub mod sound_bank;
+/// Player preferences that persist between sessions
+pub mod preferences;
+/// Maps keys to the actions scenes respond to
+pub mod input_map;
 /// Holds functions to execute draw calls
-pub mod draw_helper;
\ No newline at end of file
+pub mod draw_helper;
+/// Hosts or joins a match over the local network
+pub mod network;
+/// Plays a match by trading turn files
+pub mod play_by_mail;
@@ -1,55 +1,260 @@
-use quicksilver::sound::*;
+/*
+Background music. Each track loops on its own voice, and selecting a track played from another file
+crossfades: the outgoing voice fades out while the incoming one fades in, and the outgoing voice is
+stopped once it is silent. Tracks played from the same file share one voice, so switching between them
+only moves the volume and never stacks a second copy of the loop.
+
+On desktop voices are rodio sinks, so volume, mute and fades apply at once. Quicksilver sounds on the web
+cannot be stopped or re-volumed once they start, so there a voice replays its file one pass at a time and
+changes apply from the next pass, and an outgoing voice plays out its last pass under the new track.
+*/
+
+use crate::game_logic::scene_type::SceneType;
+
 use quicksilver::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use quicksilver::sound::Sound;
+#[cfg(not(target_arch = "wasm32"))]
+use rodio::{Sink, Source};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Cursor};
 
-pub struct MusicPlayer {
-    music: Asset<Sound>,
-    music_length: f64,
-    frame_counter: f64,
-    started: bool,
+/// Seconds a crossfade between two tracks takes
+const CROSSFADE: f64 = 1.5;
+
+/// The pieces of music the game can play
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MusicTrack {
+    Intro,
+    Game,
+    Tension,
+    Victory,
+    Defeat,
 }
 
-impl MusicPlayer {
-    /// Set up a rudimentary sound loop by passing a file name, the length in seconds, and a volume.
-    /// Volume is set here and is multiplicative, I.E. 0 is silent
-    /// 1 is identity, and 2 is double volume
-    pub fn new(path: &'static str, length_sec: f64, volume: f32) -> Result<Self> {
-        let mut music_loop = Asset::new(Sound::load(path));
-        music_loop.execute(|sound| {
-            sound.set_volume(volume);
+impl MusicTrack {
+    /// Returns the file, length in seconds and relative volume of a track
+    /// Every scene shares vgm21.wav until dedicated tracks exist, only this table needs to change when they do
+    fn source(&self) -> (&'static str, f64, f32) {
+        match self {
+            MusicTrack::Intro   => ("vgm21.wav", 19.0, 0.8),
+            MusicTrack::Game    => ("vgm21.wav", 19.0, 1.0),
+            MusicTrack::Tension => ("vgm21.wav", 19.0, 1.2),
+            MusicTrack::Victory => ("vgm21.wav", 19.0, 1.0),
+            MusicTrack::Defeat  => ("vgm21.wav", 19.0, 0.7),
+        }
+    }
+
+    /// Returns the file a track is played from
+    fn file(&self) -> &'static str {
+        self.source().0
+    }
+
+    /// Picks the track for a scene. `tension` is only used in game, `won` only in the outro and is from the
+    /// point of view of the team played on this screen
+    /// Settings keep whatever was playing, so callers should pass the scene settings were opened from
+    pub fn for_scene(scene: SceneType, tension: bool, won: bool) -> Self {
+        match scene {
+            SceneType::Intro | SceneType::Setup | SceneType::Settings => MusicTrack::Intro,
+            SceneType::Game  => if tension { MusicTrack::Tension } else { MusicTrack::Game },
+            SceneType::Outro => if won { MusicTrack::Victory } else { MusicTrack::Defeat },
+        }
+    }
+}
+
+/// A sound file, read the first time a track asks for it
+#[cfg(not(target_arch = "wasm32"))]
+struct SoundFile {
+    path: &'static str,
+    bytes: Vec<u8>,
+}
+
+/// A sound file, loaded the first time a track asks for it
+#[cfg(target_arch = "wasm32")]
+struct SoundFile {
+    path: &'static str,
+    sound: Asset<Sound>,
+}
+
+/// One file looping, dropping it stops it
+#[cfg(not(target_arch = "wasm32"))]
+struct Voice {
+    sink: Sink,
+}
+
+/// One file replayed a pass at a time, a pass that has started always plays to its end
+#[cfg(target_arch = "wasm32")]
+struct Voice {
+    sound: Sound,
+    length: f64,
+    remaining: f64, //Seconds left in the pass that is playing
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Voice {
+    /// Starts looping a file silently, files are read whole so it is always ready
+    fn start(file: &mut SoundFile, _length: f64) -> Result<Option<Self>> {
+        let device = rodio::default_output_device()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No audio output is available"))?;
+        let decoder = rodio::Decoder::new(Cursor::new(file.bytes.clone()))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", file.path, err)))?;
+
+        let mut sink = Sink::new(&device);
+        sink.set_volume(0.0);
+        sink.append(decoder.repeat_infinite());
+        Ok(Some(Self { sink }))
+    }
+
+    /// Sets the volume, heard at once
+    fn set_volume(&mut self, volume: f32) {
+        self.sink.set_volume(volume);
+    }
+
+    /// Keeps the loop going, the sink repeats the file by itself
+    fn update(&mut self, _delta: f64) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Voice {
+    /// Starts replaying a file, returns None if it has not loaded yet
+    fn start(file: &mut SoundFile, length: f64) -> Result<Option<Self>> {
+        let mut voice = None;
+        file.sound.execute(|sound| {
+            voice = Some(Self { sound: sound.clone(), length, remaining: 0.0 });
             Ok(())
         })?;
+        Ok(voice)
+    }
+
+    /// Sets the volume, heard from the next pass
+    fn set_volume(&mut self, volume: f32) {
+        self.sound.set_volume(volume);
+    }
+
+    /// Starts the next pass once the last one has ended, silent passes only keep time
+    fn update(&mut self, delta: f64) -> Result<()> {
+        self.remaining -= delta;
+        if self.remaining <= 0.0 {
+            if self.sound.volume() > 0.0 { self.sound.play()?; }
+            self.remaining = self.length;
+        }
+        Ok(())
+    }
+}
+
+/// A track's voice and how far it has faded in, from 0.0 silent to 1.0 full volume
+struct Playing {
+    track: MusicTrack,
+    voice: Voice,
+    fade: f32,
+}
+
+pub struct MusicPlayer {
+    files: Vec<SoundFile>,
+    volume: f32,
+    muted: bool,
+
+    current: Option<Playing>,
+    pending: Option<MusicTrack>, //Selected but its file has not loaded yet
+    outgoing: Vec<Playing>, //Fading out, stopped once silent
+}
 
+impl MusicPlayer {
+    /// Sets up the player with a master volume.
+    /// Volume is multiplicative, I.E. 0 is silent, 1 is identity, and 2 is double volume
+    pub fn new(volume: f32) -> Result<Self> {
         Ok(Self {
-            music: music_loop,
-            music_length: length_sec,
-            frame_counter: 0.0,
-            started: false,
+            files: vec![],
+            volume,
+            muted: false,
+            current: None,
+            pending: None,
+            outgoing: vec![],
         })
     }
 
-    /// Replays a sound if it it finished playing.
-    /// This uses the principle that draw calls happen at a certain fps to guarantee accurate replays
-    /// Very experimental, use at own risk
-    pub fn play_if_not(&mut self, curr_fps: f64) -> Result<()> {
-        let frame_inc;
-
-        //Prune edge case where fps is 0
-        if curr_fps == 0.0 { frame_inc = 1.0 / 60.0; } else { frame_inc = 1.0 / curr_fps; }
+    /// Asks for a track to be played. A track from the file already playing takes over its voice,
+    /// any other track crossfades in once its file has loaded
+    pub fn select(&mut self, track: MusicTrack) {
+        match self.current.as_mut() {
+            Some(playing) if playing.track.file() == track.file() => {
+                playing.track = track;
+                self.pending = None;
+            },
+            _ => self.pending = Some(track),
+        }
+    }
 
-        //increment counters
-        if self.frame_counter >= self.music_length {
-            self.frame_counter = frame_inc; //reset and increment
-            self.started = false; //we need to restart music
-        } else {
-            self.frame_counter += frame_inc; //increment
+    /// Advances the player by `delta` seconds, starting selected tracks and moving every fade along
+    pub fn update(&mut self, delta: f64) -> Result<()> {
+        if let Some(next) = self.pending {
+            let (path, length, _) = next.source();
+            if let Some(voice) = Voice::start(self.file(path)?, length)? {
+                let fade = if self.current.is_some() { 0.0 } else { 1.0 }; //Nothing to fade from at startup
+                if let Some(previous) = self.current.replace(Playing { track: next, voice, fade }) {
+                    self.outgoing.push(previous);
+                }
+                self.pending = None;
+            }
         }
 
-        //Play music if needed
-        if self.started == false {
-            self.started = true;
-            self.music.execute(|music| { music.play() })?;
+        let step = (delta / CROSSFADE) as f32;
+        let (volume, muted) = (self.volume, self.muted);
+        let level = |playing: &Playing| if muted { 0.0 } else { volume * playing.track.source().2 * playing.fade };
+
+        if let Some(playing) = self.current.as_mut() {
+            playing.fade = (playing.fade + step).min(1.0);
+            let playing_level = level(playing);
+            playing.voice.set_volume(playing_level);
+            playing.voice.update(delta)?;
+        }
+        for playing in self.outgoing.iter_mut() {
+            playing.fade = (playing.fade - step).max(0.0);
+            let playing_level = level(playing);
+            playing.voice.set_volume(playing_level);
+            playing.voice.update(delta)?;
         }
+        self.outgoing.retain(|playing| playing.fade > 0.0);
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Sets the master volume
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.max(0.0);
+    }
+
+    /// Mutes or unmutes music, muted tracks keep time so unmuting resumes where they would be
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Returns the file a track is played from, reading it the first time it is asked for
+    #[cfg(not(target_arch = "wasm32"))]
+    fn file(&mut self, path: &'static str) -> Result<&mut SoundFile> {
+        let index = match self.files.iter().position(|file| file.path == path) {
+            Some(index) => index,
+            None => {
+                let bytes = std::fs::read(path)?;
+                self.files.push(SoundFile { path, bytes });
+                self.files.len() - 1
+            }
+        };
+        Ok(&mut self.files[index])
+    }
+
+    /// Returns the file a track is played from, loading it the first time it is asked for
+    #[cfg(target_arch = "wasm32")]
+    fn file(&mut self, path: &'static str) -> Result<&mut SoundFile> {
+        let index = match self.files.iter().position(|file| file.path == path) {
+            Some(index) => index,
+            None => {
+                self.files.push(SoundFile { path, sound: Asset::new(Sound::load(path)) });
+                self.files.len() - 1
+            }
+        };
+        Ok(&mut self.files[index])
+    }
+}
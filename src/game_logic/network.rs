@@ -0,0 +1,448 @@
+/*
+Local network play. One machine hosts the match and is the only one that changes it: the other machine
+sends the commands its players give, and the host answers every change with the whole match as that machine's
+team may see it, so both screens always agree without either being told what its team cannot see. Messages are lines of JSON over TCP and sockets never block, so both game loops keep
+running while they wait. A client that drops keeps trying to connect again and picks up the host's match
+as it is when it gets back.
+
+Start one desktop instance with `--host [port]` and the other with `--join address[:port]`. Both desktop
+instances can instead join the same room of a match server (`src/bin/elder_server.rs`) with
+`--join address[:port] --room name`. Anyone else can watch a hosted match or a room with `--watch address[:port]`,
+seeing every change but unable to play. `--mail folder` plays by trading turn files instead, see `play_by_mail`.
+The web build has no command line, so its matches are always played on one screen.
+*/
+
+use crate::gameplay_logic::entities::PlayerType;
+use crate::gameplay_logic::game_match::{Match, MatchEvent, Command};
+use crate::gameplay_logic::match_config::MatchConfig;
+use crate::game_logic::play_by_mail::Mailbox;
+
+use quicksilver::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Port used when none is given
+pub const DEFAULT_PORT: u16 = 7878;
+/// Room joined when none is given, a host has only the one
+pub const DEFAULT_ROOM: &str = "lobby";
+/// How long a client waits between attempts to reach the host
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// Longest line read from the other end, which is dropped once it sends more without ending the line
+const MAX_LINE: usize = 1 << 20;
+/// Most bytes left waiting to be sent before the other end is given up on as no longer reading
+const MAX_QUEUED: usize = 1 << 23;
+
+/// The part this process plays in a networked match, chosen on the command line
+#[derive(Debug, PartialEq, Clone)]
+pub enum NetRole {
+    Host(u16),
+    Join { address: String, room: String },
+    Watch { address: String, room: String },
+    Mail(PathBuf), //Trades turn files through a folder instead of connecting
+}
+
+impl NetRole {
+    /// Reads `--host [port]`, `--join address[:port] [--room name]`, `--watch address[:port] [--room name]`
+    /// or `--mail folder` from the command line, None for a local match
+    pub fn from_args() -> Option<NetRole> {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |index: usize| args.get(index + 1).filter(|arg| !arg.starts_with("--")).cloned();
+        let room = args.iter().position(|arg| arg == "--room").and_then(value).unwrap_or_else(|| DEFAULT_ROOM.to_string());
+
+        for (index, arg) in args.iter().enumerate() {
+            match arg.as_str() {
+                "--host" => {
+                    let port = value(index).and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT);
+                    return Some(NetRole::Host(port));
+                },
+                "--join" | "--watch" => {
+                    let mut address = value(index).unwrap_or_else(|| "127.0.0.1".to_string());
+                    if !address.contains(':') { address = format!("{}:{}", address, DEFAULT_PORT); }
+                    return Some(if arg == "--join" { NetRole::Join { address, room } } else { NetRole::Watch { address, room } });
+                },
+                "--mail" => return Some(NetRole::Mail(PathBuf::from(value(index).unwrap_or_else(|| "mail".to_string())))),
+                _ => {},
+            }
+        }
+        None
+    }
+}
+
+/// Messages sent from a client to the host
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ClientMessage {
+    //Asks to play `team` in `room`, a server sets an empty room up with `config`
+    //A host has one room and always gives clients the team it leaves free
+    Join { room: String, team: PlayerType, config: MatchConfig },
+    Watch { room: String }, //Asks to be sent the match in `room` without playing it
+    Command(Command),
+}
+
+/// Messages sent from the host to its clients
+#[derive(Debug, Serialize, Deserialize)]
+pub enum HostMessage {
+    Welcome(PlayerType), //The team the client plays
+    State { game: Box<Match>, events: Vec<MatchEvent> },
+    Refused(String),
+}
+
+impl HostMessage {
+    /// Returns the message sending the match and what just happened as `team` may see them, spectators pass None
+    pub fn state(game: &Match, team: Option<PlayerType>, events: &[MatchEvent]) -> Result<Self> {
+        let (view, events) = game.view_for(team, events)?;
+        Ok(HostMessage::State { game: Box::new(view), events })
+    }
+}
+
+/// One end of a connection, sending and receiving lines of JSON
+pub struct Link {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    outgoing: Vec<u8>, //Bytes queued until the socket takes them
+}
+
+impl Link {
+    /// Wraps a connected stream so it never blocks
+    pub fn new(stream: TcpStream) -> Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self { stream, buffer: vec![], outgoing: vec![] })
+    }
+
+    /// Queues one message and sends as much of the queue as the socket takes now
+    /// Fails once the other end has gone or has left too much unread
+    pub fn send<T: Serialize>(&mut self, message: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(message).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        line.push(b'\n');
+
+        if self.outgoing.len() + line.len() > MAX_QUEUED {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "the other end stopped reading").into());
+        }
+        self.outgoing.extend_from_slice(&line);
+        self.flush()
+    }
+
+    /// Writes queued bytes until the socket would block
+    fn flush(&mut self) -> Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero, "connection closed").into()),
+                Ok(written) => { self.outgoing.drain(..written); },
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends what is still queued then returns every complete message received so far,
+    /// or an error once the other end has gone or sent a line longer than `MAX_LINE`
+    /// Lines that cannot be read as a message are skipped
+    pub fn receive<T: for<'de> Deserialize<'de>>(&mut self) -> Result<Vec<T>> {
+        self.flush()?;
+
+        let mut chunk = [0; 4096];
+        while self.buffer.len() <= MAX_LINE { //Anything more waits for the next call
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::ConnectionAborted, "connection closed").into()),
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let mut messages = vec![];
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            if let Ok(message) = serde_json::from_slice(&line[..end]) {
+                messages.push(message);
+            }
+        }
+
+        if self.buffer.len() > MAX_LINE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long").into());
+        }
+        Ok(messages)
+    }
+}
+
+/// A client connected to the host, with the team it plays once it has joined
+struct Peer {
+    link: Link,
+    team: Option<PlayerType>,
+    watching: bool, //Spectators are sent the match but never play
+}
+
+impl Peer {
+    /// Returns true if the peer should be sent every change to the match
+    fn following(&self) -> bool { self.team.is_some() || self.watching }
+}
+
+/// Owns the match for every machine playing it, clients can only ask it to carry out commands
+pub struct Host {
+    listener: TcpListener,
+    peers: Vec<Peer>,
+    local_team: PlayerType, //Played on the host's own screen
+}
+
+impl Host {
+    /// Starts listening for clients on every interface
+    pub fn bind(port: u16, local_team: PlayerType) -> Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, peers: vec![], local_team })
+    }
+
+    /// Returns the team played on the host's own screen
+    pub fn local_team(&self) -> PlayerType { self.local_team }
+
+    /// Sets the team played on the host's own screen, clients that have joined are told they now play the other
+    pub fn set_local_team(&mut self, team: PlayerType) {
+        if team == self.local_team { return; }
+        self.local_team = team;
+        let remote = self.remote_team();
+        let welcome = HostMessage::Welcome(remote);
+        for peer in self.peers.iter_mut().filter(|peer| peer.team.is_some()) {
+            peer.team = Some(remote);
+        }
+        self.peers.retain_mut(|peer| peer.team.is_none() || peer.link.send(&welcome).is_ok());
+    }
+
+    /// Returns the team clients play
+    fn remote_team(&self) -> PlayerType {
+        if self.local_team == PlayerType::Player1 { PlayerType::Player2 } else { PlayerType::Player1 }
+    }
+
+    /// Returns true while a client is playing
+    pub fn connected(&self) -> bool { self.peers.iter().any(|peer| peer.team.is_some()) }
+
+    /// Accepts new clients and carries out the commands clients have sent, checked against the rules
+    /// Returns what the commands caused so the host's screen can show it, every client has already been sent it
+    pub fn poll(&mut self, game: &mut Match) -> Result<Vec<MatchEvent>> {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if let Ok(link) = Link::new(stream) { self.peers.push(Peer { link, team: None, watching: false }); }
+        }
+
+        let mut events = vec![];
+        let mut changed = false;
+        let mut index = 0;
+        while index < self.peers.len() {
+            let messages = match self.peers[index].link.receive::<ClientMessage>() {
+                Ok(messages) => messages,
+                Err(_) => { self.peers.remove(index); continue; }, //Dropped, the team is free to join again
+            };
+
+            let mut reachable = true;
+            for message in messages {
+                match message {
+                    ClientMessage::Join { .. } => {
+                        let team = self.remote_team();
+                        //A client joining again keeps its team, anyone else has to wait for it to drop
+                        let taken = self.peers.iter().enumerate().any(|(other, peer)| other != index && peer.team == Some(team));
+                        let peer = &mut self.peers[index];
+                        reachable = if taken {
+                            peer.link.send(&HostMessage::Refused("Both teams are already being played".to_string())).is_ok()
+                        } else {
+                            peer.team = Some(team);
+                            peer.link.send(&HostMessage::Welcome(team)).is_ok() &&
+                                peer.link.send(&HostMessage::state(game, Some(team), &[])?).is_ok()
+                        };
+                    },
+                    ClientMessage::Watch { .. } => {
+                        let peer = &mut self.peers[index];
+                        peer.watching = true;
+                        reachable = peer.link.send(&HostMessage::state(game, None, &[])?).is_ok();
+                    },
+                    ClientMessage::Command(command) => {
+                        //Clients only play their own team, spectators have none, and only on its turn unless they are conceding
+                        let team = self.peers[index].team;
+                        let allowed = match command {
+                            Command::Concede(conceding) => Ok(team == Some(conceding)),
+                            _                           => game.team().map(|turn| team == Some(turn)),
+                        };
+                        //A command the match cannot carry out drops the client rather than stopping the host's game
+                        let applied = allowed.and_then(|allowed| if allowed { game.apply(command) } else { Ok(false) });
+                        match applied {
+                            Ok(true) => {
+                                events.extend(game.drain_events());
+                                changed = true;
+                            },
+                            Ok(false) => {},
+                            Err(err) => {
+                                eprintln!("Dropping client after {:?}: {}", command, err);
+                                reachable = false;
+                            },
+                        }
+                    },
+                }
+                if !reachable { break; }
+            }
+
+            if reachable { index += 1; } else { self.peers.remove(index); }
+        }
+
+        if changed { self.broadcast(game, &events)?; }
+        Ok(events)
+    }
+
+    /// Sends the match and what just happened to every client that has joined, each as its team may see them
+    /// Clients that cannot be reached are dropped and can join again
+    pub fn broadcast(&mut self, game: &Match, events: &[MatchEvent]) -> Result<()> {
+        let mut index = 0;
+        while index < self.peers.len() {
+            let peer = &mut self.peers[index];
+            let reachable = !peer.following() || peer.link.send(&HostMessage::state(game, peer.team, events)?).is_ok();
+            if reachable { index += 1; } else { self.peers.remove(index); }
+        }
+        Ok(())
+    }
+}
+
+/// Plays one team of a match owned by a host or watches it, reconnecting whenever the connection drops
+pub struct Client {
+    address: String,
+    room: String,
+    config: MatchConfig, //The team asked for and the match a server sets up
+    watching: bool, //Spectators are never given a team
+    link: Option<Link>,
+    team: Option<PlayerType>, //Given by the host on joining
+    retry_at: Instant,
+}
+
+impl Client {
+    /// Creates a client for the host at `address`, it connects on the first poll
+    pub fn new(address: &str, room: &str, config: MatchConfig) -> Self {
+        Self {
+            address: address.to_string(), room: room.to_string(), config, watching: false,
+            link: None, team: None, retry_at: Instant::now(),
+        }
+    }
+
+    /// Creates a spectator for the match in `room` at `address`, it can never send commands
+    pub fn spectator(address: &str, room: &str) -> Self {
+        Self { watching: true, ..Client::new(address, room, MatchConfig::default()) }
+    }
+
+    /// Returns true for a spectator
+    pub fn watching(&self) -> bool { self.watching }
+
+    /// Returns the message asking to join
+    fn join(&self) -> ClientMessage {
+        if self.watching { return ClientMessage::Watch { room: self.room.clone() }; }
+        ClientMessage::Join { room: self.room.clone(), team: self.config.player_one, config: self.config }
+    }
+
+    /// Returns the team this client plays, None until the host has welcomed it
+    pub fn team(&self) -> Option<PlayerType> { self.team }
+
+    /// Returns true while connected to the host
+    pub fn connected(&self) -> bool { self.link.is_some() }
+
+    /// Sends a command to the host, returns false if it could not be sent
+    /// Spectators never send commands
+    pub fn send(&mut self, command: Command) -> Result<bool> {
+        if self.watching { return Ok(false); }
+        let sent = match self.link.as_mut() {
+            Some(link) => link.send(&ClientMessage::Command(command)).is_ok(),
+            None       => false,
+        };
+        if !sent { self.drop_link(); }
+        Ok(sent)
+    }
+
+    /// Connects if needed and returns the newest match the host has sent, with everything that happened since the last one
+    pub fn poll(&mut self) -> Result<Option<(Match, Vec<MatchEvent>)>> {
+        if self.link.is_none() && Instant::now() >= self.retry_at {
+            self.retry_at = Instant::now() + RETRY_DELAY;
+            if let Some(address) = self.address.to_socket_addrs().ok().and_then(|mut addresses| addresses.next()) {
+                if let Ok(stream) = TcpStream::connect_timeout(&address, Duration::from_millis(200)) {
+                    let mut link = Link::new(stream)?;
+                    if link.send(&self.join()).is_ok() { self.link = Some(link); }
+                }
+            }
+        }
+
+        let messages = match self.link.as_mut().map(|link| link.receive::<HostMessage>()) {
+            Some(Ok(messages)) => messages,
+            Some(Err(_))       => { self.drop_link(); return Ok(None); },
+            None               => return Ok(None),
+        };
+
+        let mut newest = None;
+        let mut events = vec![];
+        for message in messages {
+            match message {
+                HostMessage::Welcome(team) => self.team = Some(team),
+                HostMessage::State { game, events: happened } => {
+                    events.extend(happened);
+                    newest = Some(*game);
+                },
+                HostMessage::Refused(_) => self.drop_link(),
+            }
+        }
+        Ok(newest.map(|game| (game, events)))
+    }
+
+    /// Asks the host for the match as it is now, used when this screen has thrown its copy away
+    /// A server starts the room's next match once every player in it has asked after a win
+    pub fn resync(&mut self, config: MatchConfig) {
+        self.config = config;
+        let join = self.join();
+        if let Some(link) = self.link.as_mut() {
+            if link.send(&join).is_err() { self.drop_link(); }
+        }
+    }
+
+    /// Forgets the connection so the next poll tries again after a short wait
+    fn drop_link(&mut self) {
+        self.link = None;
+        self.retry_at = Instant::now() + RETRY_DELAY;
+    }
+}
+
+/// A networked match from this process's side
+pub enum Connection {
+    Host(Host),
+    Client(Client),
+    Mail(Mailbox),
+}
+
+impl Connection {
+    /// Opens the connection for a role, the first human's team in `config` is played on this screen if it can be
+    pub fn open(role: &NetRole, config: MatchConfig) -> Result<Self> {
+        Ok(match role {
+            NetRole::Host(port)               => Connection::Host(Host::bind(*port, config.player_one)?),
+            NetRole::Join { address, room }   => Connection::Client(Client::new(address, room, config)),
+            NetRole::Watch { address, room }  => Connection::Client(Client::spectator(address, room)),
+            NetRole::Mail(folder)             => Connection::Mail(Mailbox::open(folder, config.player_one, &config)?),
+        })
+    }
+
+    /// Returns true while the other screen is connected, turn files never wait on one
+    pub fn connected(&self) -> bool {
+        match self {
+            Connection::Host(host)     => host.connected(),
+            Connection::Client(client) => client.connected() && (client.team().is_some() || client.watching()),
+            Connection::Mail(_)        => true,
+        }
+    }
+
+    /// Returns true if this screen only watches the match
+    pub fn watching(&self) -> bool {
+        matches!(self, Connection::Client(client) if client.watching())
+    }
+
+    /// Returns the team played on this screen, None while a client waits to be welcomed and for spectators
+    pub fn local_team(&self) -> Option<PlayerType> {
+        match self {
+            Connection::Host(host)     => Some(host.local_team()),
+            Connection::Client(client) => client.team(),
+            Connection::Mail(mailbox)  => Some(mailbox.team()),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+/*
+Play by mail, for players who cannot be online together. Each turn is written to a turn file holding the
+commands the team gave and the whole match as it stands after them, and the other player's game takes the
+file in once it is put into its mail folder. The files form a hash chain: every file names the hash of the
+one before it and is stamped with its own, so missing, repeated or reordered files are refused. The hashes are
+not keyed, so they only catch files damaged on the way: anyone can edit a file and stamp it again. Edits are
+caught by the replay instead. The commands are played again by the rules on the receiving side and must end
+in exactly the match the file holds, so a file cannot claim a move the rules would not allow, and files
+claiming to play the team played on the receiving screen are refused.
+
+Start the desktop game with `--mail folder`. The setup screen's "Player 1 Plays" team is played on this screen
+and whoever moves first sets the match up for both. Turn files already in the folder are taken in when the game
+starts, so a match carries on where it was left. Every match played through a folder is numbered and its turn
+files are named after it, so once a match is over the next one starts a new chain and never replays the old one.
+*/
+
+use crate::gameplay_logic::entities::PlayerType;
+use crate::gameplay_logic::game_match::{Match, MatchEvent, Command};
+use crate::gameplay_logic::match_config::MatchConfig;
+
+use quicksilver::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// First line of every turn file, changed whenever the format is
+const HEADER: &str = "elder-god-turn 1";
+/// How long to wait before looking for a turn file that was not there
+const CHECK_DELAY: Duration = Duration::from_secs(1);
+
+/// Returns the error for a turn file that cannot be taken in
+fn refused(reason: &str) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string()).into()
+}
+
+/// Returns the sha-256 of some text in hex
+fn digest(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Writes a value as json on one line
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|err| refused(&err.to_string()))
+}
+
+/// Reads a value from json
+fn from_json<T: serde::de::DeserializeOwned>(text: &str) -> Result<T> {
+    serde_json::from_str(text).map_err(|err| refused(&err.to_string()))
+}
+
+/// Returns the hash a chain starts from, the first turn file of a match set up with `config` follows on from it
+pub fn genesis(config: &MatchConfig) -> Result<String> {
+    Ok(digest(&format!("{}\n{}\n", HEADER, to_json(config)?)))
+}
+
+/// One team's turn and the match after it
+#[derive(Debug, Clone)]
+pub struct TurnFile {
+    pub sequence: u64, //Counts the files of a match from 1
+    pub previous: String, //Hash of the file before, or the match's genesis for the first
+    pub team: PlayerType, //Team that played the turn
+    pub commands: Vec<Command>,
+    pub state: Match,
+}
+
+impl TurnFile {
+    /// Returns the lines of the file above its stamp
+    fn body(&self) -> Result<String> {
+        Ok(format!("{}\nsequence {}\nprevious {}\nteam {}\ncommands {}\nstate {}\n", HEADER, self.sequence, self.previous,
+                   to_json(&self.team)?, to_json(&self.commands)?, to_json(&self.state)?))
+    }
+
+    /// Writes the turn as text stamped with its hash, returning both
+    pub fn to_text(&self) -> Result<(String, String)> {
+        let body = self.body()?;
+        let hash = digest(&body);
+        Ok((format!("{}hash {}\n", body, hash), hash))
+    }
+
+    /// Reads a turn written by `to_text`, returning it with its hash
+    /// A file that does not match its stamp was changed after it was written and is refused
+    pub fn from_text(text: &str) -> Result<(Self, String)> {
+        //Mail can turn line endings into \r\n on the way
+        let text = text.replace("\r\n", "\n");
+        let (body, stamp) = text.trim_end().rsplit_once('\n').ok_or_else(|| refused("The turn file is empty"))?;
+        let body = format!("{}\n", body);
+        let hash = stamp.strip_prefix("hash ").ok_or_else(|| refused("The turn file is not stamped"))?.to_string();
+        if digest(&body) != hash { return Err(refused("The turn file was changed after it was written")); }
+
+        let mut lines = body.lines();
+        if lines.next() != Some(HEADER) { return Err(refused("Not a turn file, or one from another version")); }
+        let mut field = |name: &str| -> Result<String> {
+            lines.next().and_then(|line| line.strip_prefix(name)).and_then(|line| line.strip_prefix(' ')).map(str::to_string)
+                .ok_or_else(|| refused(&format!("The turn file has no {}", name)))
+        };
+        let file = TurnFile {
+            sequence: field("sequence")?.parse().map_err(|_| refused("The turn file's sequence is not a number"))?,
+            previous: field("previous")?,
+            team: from_json(&field("team")?)?,
+            commands: from_json(&field("commands")?)?,
+            state: from_json(&field("state")?)?,
+        };
+        Ok((file, hash))
+    }
+}
+
+/// Keeps a match's place in its chain of turn files and the commands given since the last one
+#[derive(Debug, Clone)]
+pub struct TurnChain {
+    sequence: u64, //Files in the chain so far
+    last: String, //Hash the next file must follow on from
+    team: PlayerType, //Team giving the commands noted since the last file
+    turn: Vec<Command>,
+}
+
+impl TurnChain {
+    /// Starts the chain of a match set up with `config`
+    pub fn new(config: &MatchConfig) -> Result<Self> {
+        Ok(Self { sequence: 0, last: genesis(config)?, team: PlayerType::Undetermined, turn: vec![] })
+    }
+
+    /// Returns the number of turn files in the chain
+    pub fn sequence(&self) -> u64 { self.sequence }
+
+    /// Notes a command `team` gave that the match carried out, it goes into the next file written
+    pub fn record(&mut self, team: PlayerType, command: Command) {
+        self.team = team;
+        self.turn.push(command);
+    }
+
+    /// Returns true once the commands noted have passed the turn to the other team or ended the match
+    pub fn turn_over(&self, game: &Match) -> Result<bool> {
+        Ok(!self.turn.is_empty() && (game.winner() != PlayerType::Undetermined || game.team()? != self.team))
+    }
+
+    /// Writes the turn file for the commands noted since the last file, `game` is the match after them
+    pub fn export(&mut self, game: &Match) -> Result<String> {
+        let file = TurnFile {
+            sequence: self.sequence + 1,
+            previous: self.last.clone(),
+            team: self.team,
+            commands: self.turn.clone(),
+            state: game.clone(),
+        };
+        let (text, hash) = file.to_text()?;
+        self.sequence = file.sequence;
+        self.last = hash;
+        self.turn.clear();
+        Ok(text)
+    }
+
+    /// Takes in the next turn file of the chain, returning the match after it and what happened
+    /// `game` is the match before it, apart from the first file which sets the match up from its own setup
+    /// The file is refused if it was played by `played_here`, the team played on this screen, which is
+    /// `PlayerType::Undetermined` to take in files of either team. It is also refused unless it follows on from
+    /// the last one, its setup passes `MatchConfig::check`, its commands are all its team's to give and
+    /// allowed by the rules, they finish the turn and they lead to the match it holds
+    pub fn import(&mut self, text: &str, game: &Match, played_here: PlayerType) -> Result<(Match, Vec<MatchEvent>)> {
+        let (file, hash) = TurnFile::from_text(text)?;
+        if file.sequence != self.sequence + 1 {
+            return Err(refused(&format!("Expected turn file {} but this is turn file {}", self.sequence + 1, file.sequence)));
+        }
+        if played_here != PlayerType::Undetermined && file.team == played_here {
+            return Err(refused("The turn file plays the team played on this screen"));
+        }
+        let (mut replay, last) = if self.sequence == 0 {
+            let config = file.state.config();
+            config.check().map_err(|err| refused(&format!("The match cannot be set up: {}", err)))?;
+            (Match::new(*config)?, genesis(config)?)
+        } else {
+            (game.clone(), self.last.clone())
+        };
+        if file.previous != last { return Err(refused("The turn file does not follow on from the last one")); }
+        if file.commands.is_empty() { return Err(refused("The turn file has no commands")); }
+
+        for command in file.commands.iter() {
+            let theirs = match command {
+                Command::Concede(team) => *team == file.team,
+                _                      => replay.team()? == file.team,
+            };
+            if !theirs || !replay.apply(*command)? {
+                return Err(refused(&format!("The rules do not allow {:?}", command)));
+            }
+        }
+        if replay.winner() == PlayerType::Undetermined && replay.team()? == file.team {
+            return Err(refused("The turn file stops before the turn is over"));
+        }
+        if to_json(&replay)? != to_json(&file.state)? {
+            return Err(refused("The match in the turn file is not the one its commands lead to"));
+        }
+
+        *self = TurnChain { sequence: file.sequence, last: hash, team: file.team, turn: vec![] };
+        let events = replay.drain_events();
+        Ok((replay, events))
+    }
+}
+
+/// Returns the number of the match a turn file belongs to, None for any other file
+fn match_number(name: &str) -> Option<u32> {
+    name.strip_prefix("match-")?.split('-').next()?.parse().ok()
+}
+
+/// Trades turn files through a folder, one team of the match is played on this screen
+pub struct Mailbox {
+    folder: PathBuf,
+    number: u32, //Which match of the folder is played, its turn files are named after it
+    team: PlayerType,
+    chain: TurnChain,
+    check_at: Instant,
+    refused: Option<(String, String)>, //The last file refused and why, it is not read again until it changes
+    resuming: bool, //Taking in the files already in the folder, which include this screen's own turns
+    finished: bool, //The match has been won, the next one is played in a new chain
+}
+
+impl Mailbox {
+    /// Opens the folder turn files are traded through, `config` sets the match up unless the other team moves first
+    /// The latest match in the folder is carried on, finished or not
+    pub fn open(folder: &Path, team: PlayerType, config: &MatchConfig) -> Result<Self> {
+        fs::create_dir_all(folder)?;
+        let latest = fs::read_dir(folder)?.filter_map(|entry| entry.ok())
+            .filter_map(|entry| match_number(&entry.file_name().to_string_lossy()))
+            .max().unwrap_or(1);
+        Self::numbered(folder, latest, team, config)
+    }
+
+    /// Opens the mailbox of the next match, the one after this if it is finished and this one again if not
+    pub fn reopen(&self, team: PlayerType, config: &MatchConfig) -> Result<Self> {
+        let number = if self.finished { self.number + 1 } else { self.number };
+        Self::numbered(&self.folder, number, team, config)
+    }
+
+    /// Opens the mailbox of one match of the folder
+    fn numbered(folder: &Path, number: u32, team: PlayerType, config: &MatchConfig) -> Result<Self> {
+        Ok(Self {
+            folder: folder.to_path_buf(), number, team, chain: TurnChain::new(config)?, check_at: Instant::now(),
+            refused: None, resuming: true, finished: false,
+        })
+    }
+
+    /// Returns the team played on this screen
+    pub fn team(&self) -> PlayerType { self.team }
+
+    /// Returns why the next turn file was refused, until it is replaced by one that is taken in
+    pub fn refusal(&self) -> Option<&str> {
+        self.refused.as_ref().map(|(_, reason)| reason.as_str())
+    }
+
+    /// Returns where a turn file of the chain is kept
+    fn path(&self, sequence: u64) -> PathBuf {
+        self.folder.join(format!("match-{:04}-turn-{:04}.txt", self.number, sequence))
+    }
+
+    /// Notes a command carried out on this screen, writing the turn file once it has passed the turn on
+    pub fn record(&mut self, command: Command, game: &Match) -> Result<()> {
+        self.chain.record(self.team, command);
+        if self.chain.turn_over(game)? {
+            let text = self.chain.export(game)?;
+            fs::write(self.path(self.chain.sequence()), text)?;
+        }
+        self.finished = game.winner() != PlayerType::Undetermined;
+        Ok(())
+    }
+
+    /// Takes in the next turn file once it is in the folder, returning the match after it and what happened
+    /// A refused file is left where it is and its reason kept for `refusal` until it is replaced
+    /// This screen's own turns are only taken in from the files already there when the mailbox was opened,
+    /// once it has caught up a file playing this screen's team is refused
+    pub fn poll(&mut self, game: &Match) -> Result<Option<(Match, Vec<MatchEvent>)>> {
+        if Instant::now() < self.check_at { return Ok(None); }
+        let path = self.path(self.chain.sequence() + 1);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) if self.refused.as_ref().is_none_or(|(refused, _)| *refused != text) => text,
+            read => {
+                if read.is_err() { self.refused = None; } //A refused file that was taken away is no longer waited on
+                self.resuming = false;
+                self.check_at = Instant::now() + CHECK_DELAY;
+                return Ok(None);
+            },
+        };
+
+        let played_here = if self.resuming { PlayerType::Undetermined } else { self.team };
+        match self.chain.import(&text, game, played_here) {
+            Ok(turn) => {
+                self.refused = None;
+                self.finished = turn.0.winner() != PlayerType::Undetermined;
+                Ok(Some(turn))
+            },
+            Err(error) => {
+                let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                self.refused = Some((text, format!("{} was refused: {}", name, error)));
+                Ok(None)
+            },
+        }
+    }
+}
@@ -0,0 +1,111 @@
+/*
+Player preferences set from the settings scene. They are stored with quicksilver's saving feature,
+which writes to the user's data folder on desktop and to local storage on the web, so they survive
+between sessions.
+*/
+
+use crate::game_logic::input_map::PadSlot;
+
+use quicksilver::prelude::*;
+use quicksilver::saving::{save, load};
+use serde::{Serialize, Deserialize};
+
+/// Name the preferences are saved under
+const APP_NAME: &str = "I am the Elder God";
+const PROFILE: &str = "preferences";
+
+/// Colour schemes for meters and effects, the colorblind schemes avoid red/green and blue/yellow pairs
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Palette {
+    Standard,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Palette {
+    /// Colour of health meters
+    pub fn hp(&self) -> Color {
+        match self {
+            Palette::Standard     => Color::RED,
+            Palette::Deuteranopia => Color::from_rgba(230, 159, 0, 1.0),
+            Palette::Tritanopia   => Color::from_rgba(213, 94, 0, 1.0),
+        }
+    }
+
+    /// Colour of movement meters
+    pub fn moves(&self) -> Color {
+        match self {
+            Palette::Standard     => Color::BLUE,
+            Palette::Deuteranopia => Color::from_rgba(0, 114, 178, 1.0),
+            Palette::Tritanopia   => Color::from_rgba(0, 158, 115, 1.0),
+        }
+    }
+
+    /// Colour of damaging hits
+    pub fn hit(&self) -> Color {
+        match self {
+            Palette::Standard     => Color::RED,
+            Palette::Deuteranopia => Color::from_rgba(240, 228, 66, 1.0),
+            Palette::Tritanopia   => Color::from_rgba(204, 121, 167, 1.0),
+        }
+    }
+
+    /// Colour of fire and explosions
+    pub fn fire(&self) -> Color {
+        match self {
+            Palette::Standard     => Color::ORANGE,
+            Palette::Deuteranopia => Color::from_rgba(230, 159, 0, 1.0),
+            Palette::Tritanopia   => Color::from_rgba(213, 94, 0, 1.0),
+        }
+    }
+}
+
+/// Which team the local player usually faces, used as the default when setting up a match
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Opponent {
+    ElderGod,
+    Hunters,
+}
+
+/// Everything the player can change in the settings scene
+/// Settings missing from an older save take their default
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub music_volume: f32,
+    pub music_muted: bool,
+    pub sfx_volume: f32,
+    pub animation_speed: f32,
+    pub palette: Palette,
+    pub default_opponent: Opponent,
+    pub elder_god_pad: PadSlot,
+    pub hunters_pad: PadSlot,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.0,
+            music_muted: false,
+            sfx_volume: 1.0,
+            animation_speed: 1.0,
+            palette: Palette::Standard,
+            default_opponent: Opponent::ElderGod,
+            elder_god_pad: PadSlot::Any,
+            hunters_pad: PadSlot::Any,
+        }
+    }
+}
+
+impl Preferences {
+    /// Loads the saved preferences, falling back to the defaults if none were saved or they cannot be read
+    pub fn load() -> Self {
+        load::<Preferences>(APP_NAME, PROFILE).unwrap_or_default()
+    }
+
+    /// Saves the preferences so they are restored next session
+    pub fn save(&self) -> Result<()> {
+        save(APP_NAME, PROFILE, self)?;
+        Ok(())
+    }
+}
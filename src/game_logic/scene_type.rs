@@ -1,12 +1,16 @@
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum SceneType {
     Intro,
+    Setup,
     Game,
     Outro,
+    Settings,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum SceneReturn {
     Good,
     Finished,
+    Settings, //Open the settings scene, returning here when it finishes
+    Title, //Abandon the current scene and go back to the intro
 }
@@ -0,0 +1,144 @@
+/*
+Sound effects keyed by the game events that trigger them. Every event belongs to a volume
+category so effects and interface sounds can be balanced separately, music has its own player.
+*/
+
+use crate::gameplay_logic::entities::ActionAbility;
+
+use quicksilver::sound::*;
+use quicksilver::prelude::*;
+
+/// Played in place of any sound whose file cannot be loaded
+const FALLBACK_SOUND: &str = "Click.wav";
+
+/// The volume groups a sound can belong to
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum VolumeCategory {
+    Sfx,
+    Ui,
+}
+
+/// Things that happen in game that make a sound
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[allow(unused)]
+pub enum SoundEvent {
+    Ability(ActionAbility),
+    Damage,
+    Death,
+    SpikeStep,
+    Ignite,
+    Freeze,
+    ShieldBreak,
+    TurnChange,
+    Victory,
+    UiConfirm,
+    UiDeny,
+}
+
+impl SoundEvent {
+    /// Returns the file, category and relative volume of an event
+    /// Events share the two click sounds until dedicated effects exist, only this table needs to change when they do
+    fn source(&self) -> (&'static str, VolumeCategory, f32) {
+        match self {
+            SoundEvent::Ability(ability) => {
+                let volume = match ability {
+                    ActionAbility::Grenade | ActionAbility::Airraid | ActionAbility::Rend => 1.2,
+                    ActionAbility::Bio | ActionAbility::Shield | ActionAbility::Renew => 0.8,
+                    _ => 1.0,
+                };
+                ("Click.wav", VolumeCategory::Sfx, volume)
+            },
+            SoundEvent::Damage      => ("SoftClick.wav", VolumeCategory::Sfx, 1.0),
+            SoundEvent::Death       => ("Click.wav", VolumeCategory::Sfx, 1.5),
+            SoundEvent::SpikeStep   => ("SoftClick.wav", VolumeCategory::Sfx, 1.2),
+            SoundEvent::Ignite      => ("Click.wav", VolumeCategory::Sfx, 0.9),
+            SoundEvent::Freeze      => ("SoftClick.wav", VolumeCategory::Sfx, 0.9),
+            SoundEvent::ShieldBreak => ("Click.wav", VolumeCategory::Sfx, 1.1),
+            SoundEvent::TurnChange  => ("SoftClick.wav", VolumeCategory::Ui, 0.7),
+            SoundEvent::Victory     => ("Click.wav", VolumeCategory::Sfx, 1.5),
+            SoundEvent::UiConfirm   => ("Click.wav", VolumeCategory::Ui, 1.0),
+            SoundEvent::UiDeny      => ("SoftClick.wav", VolumeCategory::Ui, 1.0),
+        }
+    }
+}
+
+/// A sound file and whether it failed to load
+struct SoundFile {
+    path: &'static str,
+    sound: Asset<Sound>,
+    missing: bool,
+}
+
+pub struct SoundBank {
+    files: Vec<SoundFile>,
+    sfx_volume: f32,
+    ui_volume: f32,
+}
+
+impl SoundBank {
+    /// Makes a bank with every category at full volume. Files load the first time they are played
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            files: vec![],
+            sfx_volume: 1.0,
+            ui_volume: 1.0,
+        })
+    }
+
+    /// Plays the sound for an event, using the fallback sound if its own file is missing.
+    /// Missing or unplayable sounds are reported once and then skipped, they never stop the game
+    pub fn play(&mut self, event: SoundEvent) -> Result<()> {
+        let (path, category, event_volume) = event.source();
+        let volume = self.get_volume(category) * event_volume;
+        if volume <= 0.0 { return Ok(()); }
+
+        if !self.try_play(path, volume) && path != FALLBACK_SOUND {
+            self.try_play(FALLBACK_SOUND, volume);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the volume of a category, volume is multiplicative so 0 is silent and 1 is identity
+    pub fn set_volume(&mut self, category: VolumeCategory, volume: f32) {
+        let volume = volume.max(0.0);
+        match category {
+            VolumeCategory::Sfx => self.sfx_volume = volume,
+            VolumeCategory::Ui  => self.ui_volume = volume,
+        }
+    }
+
+    /// Returns the volume of a category
+    pub fn get_volume(&self, category: VolumeCategory) -> f32 {
+        match category {
+            VolumeCategory::Sfx => self.sfx_volume,
+            VolumeCategory::Ui  => self.ui_volume,
+        }
+    }
+
+    /// Plays a file at a volume, returns false if the file is missing or could not be played
+    fn try_play(&mut self, path: &'static str, volume: f32) -> bool {
+        let index = match self.files.iter().position(|file| file.path == path) {
+            Some(index) => index,
+            None => {
+                self.files.push(SoundFile { path, sound: Asset::new(Sound::load(path)), missing: false });
+                self.files.len() - 1
+            }
+        };
+
+        let file = &mut self.files[index];
+        if file.missing { return false; }
+
+        let played = file.sound.execute(|sound| {
+            sound.set_volume(volume);
+            sound.play()
+        });
+        if let Err(error) = played {
+            eprintln!("Sound {} is unavailable and will be skipped: {}", path, error);
+            file.missing = true;
+            return false;
+        }
+
+        true
+    }
+}
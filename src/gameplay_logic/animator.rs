@@ -1,42 +1,221 @@
+/*
+Time based animation primitives. Everything here is advanced with the wall-clock seconds a `Clock` measures
+between updates, so playback speed no longer depends on how fast frames are drawn or updates are run.
+*/
+
 use quicksilver::prelude::*;
-//Std
-use std::iter::Cycle;
-use std::vec::IntoIter;
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Longest step a clock hands out, longer gaps such as a scene sitting in the background are cut to this
+const MAX_DELTA: f64 = 0.25;
+
+/// A function run when an animation completes, looping animations run it at the end of every cycle
+pub type Callback = Box<dyn FnMut()>;
+
+/// Returns a point in time in seconds, only the difference between two of them means anything
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    thread_local!(static START: Instant = Instant::now());
+    START.with(|start| start.elapsed().as_secs_f64())
+}
+
+/// Returns a point in time in seconds, only the difference between two of them means anything
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    stdweb::web::Date::now() / 1000.0
+}
 
-///Helps Animation by holding a series of key and returning the next one every time a delay passes
+/// Measures the wall-clock seconds that pass between updates
+pub struct Clock {
+    last: Option<f64>,
+}
+
+impl Clock {
+    /// Makes a clock that starts measuring on its first tick
+    pub fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Returns the seconds since the last tick, the first tick returns 0.0
+    pub fn tick(&mut self) -> f64 {
+        let now = now();
+        let delta = self.last.map_or(0.0, |last| (now - last).max(0.0));
+        self.last = Some(now);
+        delta.min(MAX_DELTA)
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::new()
+    }
+}
+
+/// Decides what happens when an animation reaches its end
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Playback {
+    Once,
+    Loop,
+}
+
+/// Easing curves for tweens, these map linear progress 0.0-1.0 to eased progress 0.0-1.0
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+}
+
+impl Easing {
+    /// Applies the curve to a progress value, clamping it to 0.0-1.0 first
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear    => t,
+            Easing::EaseIn    => t * t,
+            Easing::EaseOut   => t * (2.0 - t),
+        }
+    }
+}
+
+///Plays a sequence of atlas keys where every key is shown for its own duration in seconds
 pub struct Animator {
-    curr: String,
-    keys: Cycle<IntoIter<String>>,
-    delay: f64,
-    frame_counter: f64,
+    frames: Vec<(String, f64)>,
+    playback: Playback,
+    index: usize,
+    elapsed: f64,
+    finished: bool,
+    on_complete: Option<Callback>,
 }
 
 impl Animator {
-    /// Make a rudimentary animator. This holds an a s
-    pub fn new(mut keys: Cycle<IntoIter<String>>, delay: f64) -> Result<Self> {
+    /// Makes an animator from (key, seconds) pairs
+    pub fn new(frames: Vec<(String, f64)>, playback: Playback) -> Result<Self> {
+        if frames.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Cannot create an Animator without frames").into());
+        }
+
+        Ok(Self {
+            frames,
+            playback,
+            index: 0,
+            elapsed: 0.0,
+            finished: false,
+            on_complete: None,
+        })
+    }
+
+    /// Makes an animator where every key is shown for the same amount of seconds
+    pub fn uniform(keys: Vec<String>, delay: f64, playback: Playback) -> Result<Self> {
+        Animator::new(keys.into_iter().map(|key| (key, delay)).collect(), playback)
+    }
+
+    /// Sets the function called when the animation completes
+    pub fn with_callback(mut self, callback: Callback) -> Self {
+        self.on_complete = Some(callback);
+        self
+    }
+
+    /// Advances the animation by `delta` seconds
+    pub fn update(&mut self, delta: f64) {
+        if self.finished { return; }
+        self.elapsed += delta;
+
+        //Large deltas can skip several frames at once
+        while self.elapsed >= self.frames[self.index].1 {
+            self.elapsed -= self.frames[self.index].1;
+
+            if self.index + 1 < self.frames.len() {
+                self.index += 1;
+            } else {
+                if let Some(callback) = self.on_complete.as_mut() { callback(); }
+
+                match self.playback {
+                    Playback::Loop => { self.index = 0; },
+                    Playback::Once => {
+                        self.finished = true;
+                        self.elapsed = 0.0;
+                        break;
+                    },
+                }
+            }
+
+            //Zero length frames would otherwise spin forever
+            if self.frames[self.index].1 <= 0.0 { break; }
+        }
+    }
+
+    /// Returns the key of the frame that should be drawn now
+    pub fn current_key(&self) -> &str {
+        &self.frames[self.index].0
+    }
+
+    /// True once a `Playback::Once` animation has shown its last frame, looping animations never finish
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+///Interpolates a position between two points over a duration in seconds
+pub struct Tween {
+    from: Vector,
+    to: Vector,
+    duration: f64,
+    elapsed: f64,
+    easing: Easing,
+    finished: bool,
+    on_complete: Option<Callback>,
+}
+
+impl Tween {
+    /// Makes a one-shot tween between two positions
+    pub fn new(from: Vector, to: Vector, duration: f64, easing: Easing) -> Result<Self> {
         Ok(Self {
-            curr: keys.next().expect("cannot load first key Animator"),
-            keys,
-            delay,
-            frame_counter: 0.0,
+            from, to, duration, easing,
+            elapsed: 0.0,
+            finished: false,
+            on_complete: None,
         })
     }
 
-    /// Returns the next animation key if delay has passed
-    pub fn next_if_not(&mut self, curr_fps: f64) -> Result<&String> {
-        let frame_inc;
+    /// Sets the function called when the tween completes
+    pub fn with_callback(mut self, callback: Callback) -> Self {
+        self.on_complete = Some(callback);
+        self
+    }
 
-        //Prune edge case where fps is 0
-        if curr_fps == 0.0 { frame_inc = 1.0 / 60.0; } else { frame_inc = 1.0 / curr_fps; }
+    /// Advances the tween by `delta` seconds
+    pub fn update(&mut self, delta: f64) {
+        if self.finished { return; }
+        self.elapsed += delta;
 
-        //increment counters
-        if self.frame_counter >= self.delay {
-            self.frame_counter = frame_inc; //reset and increment
-            self.curr = self.keys.next().expect("Cannot find nect key in Animator.");
-        } else {
-            self.frame_counter += frame_inc; //increment
+        if self.elapsed >= self.duration {
+            self.elapsed = self.duration;
+            self.finished = true;
+            if let Some(callback) = self.on_complete.as_mut() { callback(); }
         }
+    }
 
-        Ok(&self.curr)
+    /// Returns linear progress between 0.0 and 1.0
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 { 1.0 } else { (self.elapsed / self.duration) as f32 }
     }
-}
\ No newline at end of file
+
+    /// Returns the eased position for the current progress
+    pub fn value(&self) -> Vector {
+        let t = self.easing.apply(self.progress());
+        self.from + (self.to - self.from) * t
+    }
+
+    /// Returns the position the tween ends at
+    pub fn target(&self) -> Vector {
+        self.to
+    }
+
+    /// True once the tween has reached its target
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
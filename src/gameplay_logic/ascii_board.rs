@@ -0,0 +1,84 @@
+/*
+Draws a match as plain text for debugging and for playing over a terminal. Every cell takes two characters:
+the land's key, or the initial of the entity standing there, followed by the condition's key or a space while
+the land is normal. Entity initials are lowercase so they cannot be mistaken for land keys.
+*/
+
+use crate::gameplay_logic::entities::{Entity, PlayerType, Status};
+use crate::gameplay_logic::game_board::GameBoard;
+use crate::gameplay_logic::game_match::Match;
+use crate::gameplay_logic::gameplay_type::{TerrainStatus, FULL_MOVE};
+
+use quicksilver::prelude::*;
+
+/// Returns the name a team goes by
+pub fn team_name(team: PlayerType) -> &'static str {
+    match team {
+        PlayerType::Player1      => "Elder God",
+        PlayerType::Player2      => "Hunters",
+        PlayerType::Undetermined => "Nobody",
+    }
+}
+
+/// Returns the character an entity is drawn as, the lowercase first letter of its class
+pub fn initial(entity: &Entity) -> Result<char> {
+    Ok(entity.get_class()?.key().chars().next().unwrap_or('?').to_ascii_lowercase())
+}
+
+/// Draws the board with the living entities standing on it, one line per row
+pub fn render_board(board: &GameBoard, entities: &[Entity]) -> Result<String> {
+    let mut text = String::new();
+    for coord in board.coords() {
+        let cell = board.cell(coord)?;
+        let pos = cell.get_pos()?;
+        let mut standing = None;
+        for entity in entities.iter().filter(|entity| !entity.is_dead()) {
+            if entity.get_pos()? == pos { standing = Some(initial(entity)?); }
+        }
+        let land = cell.get_land()?.key().expect("No known key for tile ascii_board::render_board");
+        let cond = match cell.get_cond()? {
+            TerrainStatus::Normal => " ",
+            cond                  => cond.key().expect("No known key for condition ascii_board::render_board"),
+        };
+        match standing {
+            Some(letter) => text.push(letter),
+            None         => text.push_str(land),
+        }
+        text.push_str(cond);
+
+        //Rows end with the last cell, not the condition's padding
+        if coord.x as usize + 1 == board.width() {
+            text.truncate(text.trim_end().len());
+            text.push('\n');
+        }
+    }
+    Ok(text)
+}
+
+/// Draws a whole match: whose turn it is, the board and a line for every entity
+pub fn render_match(game: &Match) -> Result<String> {
+    let mut text = if game.winner() != PlayerType::Undetermined {
+        format!("Round {}, the {} win\n", game.round(), team_name(game.winner()))
+    } else {
+        format!("Round {}, {} ({}) to play, {} moves and {} actions left\n", game.round(), game.current_entity().get_class()?.key(),
+                team_name(game.team()?), game.moves() as f32 / FULL_MOVE as f32, game.actions())
+    };
+    text.push_str(&render_board(game.board(), game.entities())?);
+
+    for (index, entity) in game.entities().iter().enumerate() {
+        let mut entity = *entity;
+        let pos = entity.get_pos()?;
+        let max_hp = *entity.get_stats()?.get_hp();
+        let hp = *entity.get_curr_stats()?.get_hp();
+        let marker = if index == game.current() && game.winner() == PlayerType::Undetermined { '>' } else { ' ' };
+        text.push_str(&format!("{} {} {:<8} {:<10} hp {:>5.1}/{:<5.1} at {},{}", marker, initial(&entity)?,
+                               entity.get_class()?.key(), team_name(*entity.get_player()?), hp, max_hp, pos.x, pos.y));
+        match entity.get_status()? {
+            Status::Normal => {},
+            status         => text.push_str(&format!(" {:?}", status)),
+        }
+        for effect in entity.get_effects() { text.push_str(&format!(" {:?}", effect)); }
+        text.push('\n');
+    }
+    Ok(text)
+}
@@ -0,0 +1,85 @@
+/*
+Whole number positions on the board. Entities and cells keep their positions as `Vector`s because that is what
+gets drawn, the rules turn them into a `Coord` before they look at the board. A `Coord` can lie off the board,
+`GameBoard::get` is where that is found out, so the board is never indexed with a position nobody checked.
+*/
+
+use crate::gameplay_logic::gameplay_type::Direction;
+
+use quicksilver::geom::Vector;
+
+/// The order neighbours are listed in
+const NEIGHBOUR_DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+
+/// A cell position, x counts columns from the left and y counts rows from the top
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coord {
+    pub fn new(x: i32, y: i32) -> Self { Self { x, y } }
+
+    /// Returns the cell one step away in a direction
+    pub fn step(self, direction: Direction) -> Self {
+        self.offset(direction, 1)
+    }
+
+    /// Returns the cell `distance` steps away in a direction
+    pub fn offset(self, direction: Direction, distance: i32) -> Self {
+        match direction {
+            Direction::Up    => Coord::new(self.x, self.y - distance),
+            Direction::Down  => Coord::new(self.x, self.y + distance),
+            Direction::Left  => Coord::new(self.x - distance, self.y),
+            Direction::Right => Coord::new(self.x + distance, self.y),
+        }
+    }
+
+    /// Returns how many steps apart two cells are, moving up, down, left and right
+    pub fn distance(self, other: Coord) -> u32 {
+        ((self.x - other.x).abs() + (self.y - other.y).abs()) as u32
+    }
+
+    /// Returns the cells up, left, down and right of this one
+    pub fn neighbours(self) -> impl Iterator<Item = Coord> {
+        NEIGHBOUR_DIRECTIONS.iter().map(move |direction| self.step(*direction))
+    }
+
+    /// Returns the cells exactly `radius` steps away, row by row from the top like the board
+    pub fn ring(self, radius: u32) -> impl Iterator<Item = Coord> {
+        let radius = radius as i32;
+        (-radius..=radius).flat_map(move |dy| {
+            let reach = radius - dy.abs();
+            let columns = if reach == 0 { vec![0] } else { vec![-reach, reach] };
+            columns.into_iter().map(move |dx| Coord::new(self.x + dx, self.y + dy))
+        })
+    }
+
+    /// Returns this cell and every cell up to `radius` steps away, row by row from the top like the board
+    pub fn within(self, radius: u32) -> impl Iterator<Item = Coord> {
+        let radius = radius as i32;
+        (-radius..=radius).flat_map(move |dy| {
+            let reach = radius - dy.abs();
+            (-reach..=reach).map(move |dx| Coord::new(self.x + dx, self.y + dy))
+        })
+    }
+
+    /// Returns the cells in a straight line starting next to this one, the line never ends on its own
+    pub fn line(self, direction: Direction) -> impl Iterator<Item = Coord> {
+        (1..).map(move |distance| self.offset(direction, distance))
+    }
+}
+
+impl From<Vector> for Coord {
+    /// Positions are whole numbers kept as floats, each is rounded to the cell it stands for
+    fn from(pos: Vector) -> Self {
+        Coord::new(pos.x.round() as i32, pos.y.round() as i32)
+    }
+}
+
+impl From<Coord> for Vector {
+    fn from(coord: Coord) -> Self {
+        Vector::new(coord.x as f32, coord.y as f32)
+    }
+}
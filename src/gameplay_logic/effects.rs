@@ -0,0 +1,187 @@
+/*
+Visual effects played over the board when an ability is used. Positions are in board cells and
+converted to pixels when drawn, timing is driven by the same elapsed seconds as the animator.
+*/
+
+use crate::gameplay_logic::animator::{Tween, Easing, Callback};
+use crate::gameplay_logic::entities::ActionAbility;
+use crate::game_logic::preferences::Palette;
+
+use quicksilver::prelude::*;
+use rand::Rng;
+
+/// Pixel size of a board cell
+const CELL_PX: f32 = 40.0;
+
+/// The shapes an effect can take
+pub enum EffectShape {
+    /// A bolt travelling in a straight line, leaving a trail behind it
+    Projectile { path: Tween },
+    /// A lobbed shot that rises `height` cells at the middle of its flight
+    Arc { path: Tween, height: f32 },
+    /// A disc that grows to `radius` cells and fades
+    Burst { center: Vector, radius: f32 },
+    /// A small blast on a single cell
+    Impact { center: Vector },
+    /// A speck moving along a path
+    Particle { path: Tween },
+    /// A ring of shards that expands to `radius` cells
+    Ring { center: Vector, radius: f32 },
+}
+
+/// A single effect that waits `delay` seconds and then plays for `duration` seconds
+pub struct Effect {
+    shape: EffectShape,
+    color: Color,
+    delay: f64,
+    duration: f64,
+    elapsed: f64,
+    on_complete: Option<Callback>,
+}
+
+impl Effect {
+    /// Makes an effect, paths inside the shape should last `duration` seconds
+    pub fn new(shape: EffectShape, color: Color, delay: f64, duration: f64) -> Self {
+        Self { shape, color, delay, duration, elapsed: 0.0, on_complete: None }
+    }
+
+    /// Sets the function called once the effect has played out
+    pub fn with_callback(mut self, callback: Callback) -> Self {
+        self.on_complete = Some(callback);
+        self
+    }
+
+    /// Advances the effect by `delta` seconds, paths only start moving once the delay has passed
+    pub fn update(&mut self, delta: f64) {
+        let mut delta = delta;
+        if self.delay > 0.0 {
+            self.delay -= delta;
+            if self.delay > 0.0 { return; }
+            //Only the part of the delta that was not spent waiting plays the effect
+            delta = -self.delay;
+            self.delay = 0.0;
+        }
+        self.elapsed += delta;
+
+        match &mut self.shape {
+            EffectShape::Projectile { path } |
+            EffectShape::Arc { path, .. } |
+            EffectShape::Particle { path }   => path.update(delta),
+            _                                => {},
+        }
+
+        if self.is_finished() {
+            if let Some(mut callback) = self.on_complete.take() { callback(); }
+        }
+    }
+
+    /// True once the effect has played out
+    pub fn is_finished(&self) -> bool {
+        self.delay <= 0.0 && self.elapsed >= self.duration
+    }
+
+    /// Draws the effect, `origin` is the pixel position of the board's top left corner
+    pub fn draw(&self, window: &mut Window, origin: Vector, layer: f32) {
+        if self.delay > 0.0 { return; }
+        let t = (self.elapsed / self.duration).min(1.0) as f32;
+        let to_px = |cell: Vector| origin + cell * CELL_PX + Vector::new(CELL_PX / 2.0, CELL_PX / 2.0);
+
+        match &self.shape {
+            EffectShape::Projectile { path } => {
+                let head = to_px(path.value());
+                let tail = to_px(path.value() - (path.target() - path.value()) * 0.25);
+                window.draw_ex(&Line::new(tail, head).with_thickness(3.0), Col(self.color.with_alpha(0.6)), Transform::IDENTITY, layer);
+                window.draw_ex(&Circle::new(head, 5.0), Col(self.color), Transform::IDENTITY, layer + 0.01);
+            },
+            EffectShape::Arc { path, height } => {
+                //Parabola peaking at the middle of the flight
+                let lift = 4.0 * height * path.progress() * (1.0 - path.progress());
+                let pos = to_px(path.value() - Vector::new(0.0, lift));
+                window.draw_ex(&Circle::new(pos, 6.0), Col(self.color), Transform::IDENTITY, layer);
+            },
+            EffectShape::Burst { center, radius } => {
+                window.draw_ex(&Circle::new(to_px(*center), radius * CELL_PX * t),
+                               Col(self.color.with_alpha(1.0 - t)), Transform::IDENTITY, layer);
+            },
+            EffectShape::Impact { center } => {
+                window.draw_ex(&Circle::new(to_px(*center), CELL_PX * 0.5 * t),
+                               Col(self.color.with_alpha(1.0 - t)), Transform::IDENTITY, layer);
+                window.draw_ex(&Circle::new(to_px(*center), CELL_PX * 0.2 * (1.0 - t)),
+                               Col(Color::WHITE.with_alpha(1.0 - t)), Transform::IDENTITY, layer + 0.01);
+            },
+            EffectShape::Particle { path } => {
+                window.draw_ex(&Circle::new(to_px(path.value()), 3.0 + 2.0 * (1.0 - t)),
+                               Col(self.color.with_alpha(0.4 + 0.6 * t)), Transform::IDENTITY, layer);
+            },
+            EffectShape::Ring { center, radius } => {
+                //Shards spaced evenly around the circumference
+                let shards = 16;
+                for shard in 0..shards {
+                    let angle = 360.0 / shards as f32 * shard as f32;
+                    let pos = to_px(*center) + Vector::from_angle(angle) * (radius * CELL_PX * t);
+                    window.draw_ex(&Circle::new(pos, 4.0), Col(self.color.with_alpha(1.0 - t * 0.8)), Transform::IDENTITY, layer);
+                }
+            },
+        }
+    }
+}
+
+/// Returns the cell furthest from `origin` out of `cells`, or `origin` if there are none
+fn furthest(origin: Vector, cells: &[Vector]) -> Vector {
+    cells.iter().fold(origin, |best, cell| {
+        if (*cell - origin).len2() > (best - origin).len2() { *cell } else { best }
+    })
+}
+
+/// Returns the average position of `cells`, or `fallback` if there are none
+fn centroid(cells: &[Vector], fallback: Vector) -> Vector {
+    if cells.is_empty() { return fallback; }
+    cells.iter().fold(Vector::ZERO, |sum, cell| sum + *cell) / cells.len() as f32
+}
+
+/// Builds the effects for an ability used from `caster` on `targets`
+/// Air Raid expects only the cells that were actually hit. Abilities without a dedicated effect return nothing
+/// Hits and explosions take their colours from `palette` so they stay readable for colorblind players
+pub fn for_ability(ability: ActionAbility, caster: Vector, targets: &[Vector], palette: Palette) -> Result<Vec<Effect>> {
+    let mut rng = rand::thread_rng();
+    let mut effects = vec![];
+
+    match ability {
+        ActionAbility::Pierce | ActionAbility::Spear => {
+            let end = furthest(caster, targets);
+            let duration = 0.08 * (end - caster).len().max(1.0) as f64;
+            let color = if ability == ActionAbility::Pierce { Color::YELLOW } else { Color::from_rgba(200, 200, 210, 1.0) };
+            effects.push(Effect::new(EffectShape::Projectile { path: Tween::new(caster, end, duration, Easing::Linear)? },
+                                     color, 0.0, duration));
+        },
+        ActionAbility::Grenade => {
+            let center = centroid(targets, caster);
+            effects.push(Effect::new(EffectShape::Arc { path: Tween::new(caster, center, 0.4, Easing::Linear)?, height: 1.5 },
+                                     Color::from_rgba(60, 70, 50, 1.0), 0.0, 0.4));
+            effects.push(Effect::new(EffectShape::Burst { center, radius: 1.5 }, palette.fire(), 0.4, 0.35));
+        },
+        ActionAbility::Airraid => {
+            //Each hit lands at its own time so the strike reads as scattered
+            for target in targets {
+                effects.push(Effect::new(EffectShape::Impact { center: *target }, palette.hit(),
+                                         rng.gen_range(0.0, 0.6), 0.3));
+            }
+        },
+        ActionAbility::Drain => {
+            for target in targets {
+                for particle in 0..3 {
+                    let jitter = Vector::new(rng.gen_range(-0.3, 0.3), rng.gen_range(-0.3, 0.3));
+                    let path = Tween::new(*target + jitter, caster, 0.45, Easing::EaseIn)?;
+                    effects.push(Effect::new(EffectShape::Particle { path }, Color::from_rgba(140, 40, 160, 1.0),
+                                             0.1 * particle as f64, 0.45));
+                }
+            }
+        },
+        ActionAbility::Decoy => {
+            effects.push(Effect::new(EffectShape::Ring { center: caster, radius: 1.5 }, Color::from_rgba(170, 220, 255, 1.0), 0.0, 0.5));
+        },
+        _ => {},
+    }
+
+    Ok(effects)
+}
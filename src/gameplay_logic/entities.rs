@@ -1,13 +1,15 @@
 /*
 Here we write the character classes that inhabit the field
 */
+use crate::gameplay_logic::coord::Coord;
 use crate::gameplay_logic::game_board::GameBoard;
 use crate::gameplay_logic::gameplay_type::*;
-use crate::scenes::game::Direction;
 
 use quicksilver::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, VecDeque};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum ActionAbility {
     Bio, Shield, Renew,
     Pierce, Grenade, Airraid,
@@ -15,14 +17,14 @@ pub enum ActionAbility {
     Drain, Decoy, Rend,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum PlayerType {
     Player1,
     Player2,
     Undetermined,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 #[allow(unused)]
 pub enum ClassType {
     Support,
@@ -47,11 +49,24 @@ impl ClassType {
              ClassType::NPC => {"-"} //This maps to a blank TerrainStatus
         }
     }
+
+    /// How many cells away the class can see in fog of war
+    pub fn vision(&self) -> u32 {
+        match self {
+            ClassType::Support => 5,
+            ClassType::Assault => 5,
+            ClassType::Trapper => 6,
+            ClassType::Wraith  => 6,
+            ClassType::Kraken  => 5,
+            ClassType::Elder   => 7,
+            ClassType::NPC     => 0,
+        }
+    }
 }
 
 /// Describes the attributes of a particular class
 #[allow(unused)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Attributes {
     hp: f32,
     speed: f32,
@@ -61,6 +76,10 @@ pub struct Attributes {
     exp: f32,
 }
 
+impl Default for Attributes {
+    fn default() -> Self { Self::new() }
+}
+
 #[allow(unused)]
 impl Attributes {
     /// Initialize universal stats
@@ -181,16 +200,49 @@ impl Attributes {
     }
 }
 
-///Status effects a player can have
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Turns a downed hunter has to be revived before they die
+pub const BLEED_OUT_TURNS: u32 = 3;
+
+///Whether a player is still in the match
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Status {
     Normal,
-    Crippled,
+    Downed, //A hunter at 0 hp, cannot act and dies when the timer runs out unless revived
     Dead,
 }
 
+///Status effects a player can have, any number can be active at once and each counts down its own turns
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusEffect {
+    Crippled,     //Halves speed
+    Stunned,      //No moves or actions
+    Bleeding,     //Loses hp every turn and a quarter of power
+    Rooted,       //No moves
+    Marked,       //Loses armor
+    Regenerating, //Gains hp every turn
+    Invisible,    //Hidden from the other team until it acts
+}
+
+/// Every status effect, in the order durations are stored
+pub const STATUS_EFFECTS: [StatusEffect; 7] = [
+    StatusEffect::Crippled, StatusEffect::Stunned, StatusEffect::Bleeding, StatusEffect::Rooted,
+    StatusEffect::Marked, StatusEffect::Regenerating, StatusEffect::Invisible,
+];
+
+impl StatusEffect {
+    /// Index of the effect's duration in an entity
+    fn index(&self) -> usize {
+        STATUS_EFFECTS.iter().position(|effect| effect == self).expect("Every effect is listed")
+    }
+
+    /// True for effects that cleansing removes
+    pub fn is_harmful(&self) -> bool {
+        !matches!(self, StatusEffect::Regenerating | StatusEffect::Invisible)
+    }
+}
+
 /// This models the most universal class
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Entity {
     player: PlayerType,
     class: ClassType,
@@ -201,6 +253,8 @@ pub struct Entity {
     invincible: bool,
     status: Status,
     status_timer: u32,
+    effects: [u32; 7], //Turns left on each status effect, by `STATUS_EFFECTS` order
+    cover: f32, //Armor given by the land the entity stands on
     tangible: bool, //Will this Entity be pass-through?
 }
 
@@ -216,6 +270,8 @@ impl Entity{
             level, pos, invincible,
             status: Status::Normal,
             status_timer: 0,
+            effects: [0; 7],
+            cover: 0.0,
             tangible: true,
         })
     }
@@ -245,14 +301,92 @@ impl Entity{
         Ok(())
     }
     /// Decrements the status timer, and resets status to normal if it reaches 0
+    /// Downed entities bleed out and die instead
     pub fn decrement_timer(&mut self) {
         if self.status_timer > 0 {
             self.status_timer -= 1;
-            if self.status_timer == 0 {
+            if self.status_timer == 0 && self.status == Status::Downed {
+                self.set_status(Status::Dead, 0);
+            } else if self.status_timer == 0 {
                 self.set_status(Status::Normal, 0);
             }
         }
     }
+    /// Applies a status effect for a number of the entity's turns, an effect that is already active keeps the longer duration
+    pub fn apply_effect(&mut self, effect: StatusEffect, turns: u32) -> Result<()> {
+        let remaining = &mut self.effects[effect.index()];
+        *remaining = (*remaining).max(turns);
+        self.refresh_stats()
+    }
+    /// Removes a status effect
+    pub fn remove_effect(&mut self, effect: StatusEffect) -> Result<()> {
+        self.effects[effect.index()] = 0;
+        self.refresh_stats()
+    }
+    /// Removes every harmful status effect
+    pub fn cleanse(&mut self) -> Result<()> {
+        for effect in STATUS_EFFECTS.iter().filter(|effect| effect.is_harmful()) {
+            self.effects[effect.index()] = 0;
+        }
+        self.refresh_stats()
+    }
+    /// True if a status effect is active
+    pub fn has_effect(&self, effect: StatusEffect) -> bool { self.effects[effect.index()] > 0 }
+    /// Returns the active status effects
+    pub fn get_effects(&self) -> Vec<StatusEffect> {
+        STATUS_EFFECTS.iter().filter(|effect| self.has_effect(**effect)).copied().collect()
+    }
+    /// Applies the start of turn effects, bleeding and regeneration change hp and every effect loses a turn
+    /// Stats are computed with the effects active at the start of the turn so a one turn effect still applies
+    pub fn tick_effects(&mut self) -> Result<()> {
+        let max_hp = *self.get_stats()?.get_hp();
+        if self.has_effect(StatusEffect::Bleeding)     { self.add_checked_hp(-max_hp * 0.05)?; }
+        if self.has_effect(StatusEffect::Regenerating) { self.add_checked_hp(max_hp * 0.1)?; }
+        self.refresh_stats()?;
+
+        for remaining in self.effects.iter_mut() {
+            if *remaining > 0 { *remaining -= 1; }
+        }
+        Ok(())
+    }
+    /// Recomputes current speed, armor, power and actions from the base stats and the active effects, keeping current hp
+    fn refresh_stats(&mut self) -> Result<()> {
+        let base = self.stats;
+        let mut speed = base.speed;
+        let mut armor = base.armor;
+        let mut power = base.power;
+        let mut actions = base.actions;
+
+        if self.has_effect(StatusEffect::Crippled) { speed = (speed / 2.0).floor(); }
+        if self.has_effect(StatusEffect::Rooted)   { speed = 0.0; }
+        if self.has_effect(StatusEffect::Stunned)  { speed = 0.0; actions = 0.0; }
+        if self.has_effect(StatusEffect::Marked)   { armor = (armor - 2.0).max(0.0); }
+        if self.has_effect(StatusEffect::Bleeding) { power *= 0.75; }
+        armor += self.cover;
+
+        self.curr_stats = Attributes::new_custom_stats(self.curr_stats.hp, speed, armor, power, actions, self.curr_stats.exp)?;
+        Ok(())
+    }
+    /// Sets the armor given by the land the entity stands on
+    pub fn set_cover(&mut self, armor: f32) -> Result<()> {
+        if self.cover != armor {
+            self.cover = armor;
+            self.refresh_stats()?;
+        }
+        Ok(())
+    }
+    /// Downs a hunter that has run out of hp, returns true if they were just downed
+    /// The monster is never downed, it loses as soon as its hp runs out
+    pub fn knock_down(&mut self) -> Result<bool> {
+        let downable = self.player == PlayerType::Player2 && self.status != Status::Downed && self.status != Status::Dead;
+        if downable && *self.get_curr_stats()?.get_hp() <= 0.0 {
+            self.set_status(Status::Downed, BLEED_OUT_TURNS)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+    /// Returns true if the entity cannot take part in the match anymore
+    pub fn is_dead(&self) -> bool { self.status == Status::Dead }
     /// Resets player status to normal
     pub fn reset_status(&mut self) {
         self.set_status(Status::Normal, 0);
@@ -283,18 +417,21 @@ impl Entity{
     /// Check to see if this entity can move into a given location
     pub fn can_move(&self, location: Vector, board: &GameBoard, players: &Vec<Entity>) -> Result<bool> {
         let mut movable = true; //assume truth and attempt to disprove
-        let cell = board.get_board()?[location.y as usize][location.x as usize];
+        let cell = match board.get(Coord::from(location)) {
+            Some(cell) => *cell,
+            None       => return Ok(false), //Nothing can leave the board
+        };
         let land = *cell.get_land()?;
         let cond = *cell.get_cond()?;
 
         if self.tangible { //If we are tangible we need to check for tangible barriers
-            for player in players { //Check all players to see if there is a tangible player in location
-                if player.get_pos()? == location {
+            for player in players { //Check all players to see if there is a tangible player in location, the dead leave no body
+                if player.get_pos()? == location && !player.is_dead() {
                     movable = false;
                 }
             }
 
-            if land == Terrain::Empty { //Check for impassable Terrain types
+            if land.rules().move_cost.is_none() { //Check for impassable Terrain types
                 movable = false;
             }
 
@@ -315,8 +452,33 @@ impl Entity{
         Ok(movable)
     }
 
+    /// Returns every location the entity could reach this turn with `moves` half moves left
+    pub fn reachable(&self, moves: u32, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
+        let mut best: HashMap<Coord, u32> = HashMap::new(); //Most half moves left on arriving at each cell
+        let mut frontier = VecDeque::new();
+        frontier.push_back((Coord::from(self.pos), moves));
+
+        while let Some((pos, left)) = frontier.pop_front() {
+            for next in pos.neighbours() {
+                if !self.can_move(next.into(), board, players)? { continue; }
+
+                let rules = board.cell(next)?.get_land()?.rules();
+                if !rules.can_enter(left) { continue; }
+                let after = rules.moves_after(left);
+                if best.get(&next).is_none_or(|prev| after > *prev) {
+                    best.insert(next, after);
+                    if after > 0 { frontier.push_back((next, after)); }
+                }
+            }
+        }
+
+        Ok(board.coords().filter(|coord| best.contains_key(coord)).map(Vector::from).collect())
+    }
+
     /// Returns true if an ability from 1-3, inclusively, can be used. False otherwise
     pub fn can_act(&self, action_index: u32, _board: &GameBoard, _players: &Vec<Entity>) -> Result<bool> {
+        if self.status == Status::Downed || self.status == Status::Dead { return Ok(false); }
+
         let actable = match self.class {
             ClassType::Support  => {
                 match action_index {
@@ -342,7 +504,7 @@ impl Entity{
                     _ => { false }
                 }
             },
-            ClassType::Wraith   => { //Monsters always have access to all class abilities.
+            ClassType::Wraith | ClassType::Kraken | ClassType::Elder => { //Monsters always have access to all class abilities.
                 match action_index {
                     1 => { true },
                     2 => { true },
@@ -390,7 +552,7 @@ impl Entity{
                         ActionAbility::Grenade
                     },
                     3 => {
-                        targets = self.directed_line_radial(3, 3, direction, board, players)?;
+                        targets = self.directed_line_radial_sky(3, 3, direction, board, players)?;
                         ActionAbility::Airraid
                     },
                     _ => { panic!("Unknown Assault Ability Number") }
@@ -413,7 +575,8 @@ impl Entity{
                     _ => { panic!("Unknown Trapper Ability Number") }
                 }
             },
-            ClassType::Wraith   => {
+            //Later monster stages keep the Wraith's abilities
+            ClassType::Wraith | ClassType::Kraken | ClassType::Elder => {
                 match action_index {
                     1 => {
                         targets = self.adjacent_range(1, board, players)?;
@@ -441,23 +604,20 @@ impl Entity{
 impl Entity {
     /// Returns true if the passed location is attackable, false otherwise
     pub fn can_attack(&self, location: Vector, board: &GameBoard, _players: &Vec<Entity>) -> bool {
-        let mut attackable = true; //assume truth and attempt to disprove
-        let cell = board.get_board().unwrap()[location.y as usize][location.x as usize];
-        let land = *cell.get_land().unwrap();
-        let _cond = *cell.get_cond().unwrap();
-
         // Offboard spaces should always be unselectable
-        if land == Terrain::Empty {
-            attackable = false;
+        match board.get(Coord::from(location)) {
+            Some(cell) => *cell.get_land().unwrap() != Terrain::Empty,
+            None       => false,
         }
-
-        attackable
     }
 
     /// Returns true if the passed location is grappable
     pub fn can_grapple(&self, location: Vector, board: &GameBoard, players: &Vec<Entity>) -> bool {
         let mut grappable= false;
-        let cell = board.get_board().unwrap()[location.y as usize][location.x as usize];
+        let cell = match board.get(Coord::from(location)) {
+            Some(cell) => *cell,
+            None       => return false,
+        };
         let land = *cell.get_land().unwrap();
         let cond = *cell.get_cond().unwrap();
 
@@ -480,17 +640,11 @@ impl Entity {
     /// Returns a list of attackable coordinates of all characters controlled by a player
     pub fn list_range_ally(&self, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
         let mut targetable = vec![];
-        //let player_pos = self.pos;
 
         for player in players { // For all players
-            for row in board.get_board()? {
-                for cell in row { //The cell is on the board
-                    if self.can_attack(cell.get_pos()?, board, players) { //The cell can be attacked
-                        if player.get_player()? == self.get_player()? && cell.get_pos()? == player.get_pos()? { //The player is an ally and is on the cell being examined
-                            targetable.push(cell.get_pos()?);
-                        }
-                    }
-                }
+            let pos = player.get_pos()?;
+            if player.get_player()? == self.get_player()? && self.can_attack(pos, board, players) { //The player is an ally on a cell that can be attacked
+                targetable.push(pos);
             }
         }
 
@@ -499,131 +653,36 @@ impl Entity {
 
     /// Returns a list of attackable coordinates adjacent to the player up to the range specified
     pub fn adjacent_range(&self, range: u32, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        for row in board.get_board()? {
-            for cell in row {
-                let distance = cell.get_pos()? - player_pos;
-                if distance.x.abs() + distance.y.abs() <= range as f32 { //The cell is in range of the player
-                    if player_pos != cell.get_pos()? { //The cell is not on the player
-                        if self.can_attack(cell.get_pos()?, board, players) { //The cell can be attacked
-                            targetable.push(cell.get_pos()?);
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(targetable)
+        let origin = Coord::from(self.pos);
+        Ok(self.attackable(origin.within(range).filter(|cell| *cell != origin), board, players)) //The cell is not on the player
     }
 
     /// Returns a list of attackable coordinates adjacent to the player up to the range specified, including the player
     pub fn adjacent_radial(&self, range: u32, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        for row in board.get_board()? {
-            for cell in row {
-                let distance = cell.get_pos()? - player_pos;
-                if distance.x.abs() + distance.y.abs() <= range as f32 { //The cell is in range of the player
-                    if self.can_attack(cell.get_pos()?, board, players) { //The cell can be attacked
-                        targetable.push(cell.get_pos()?);
-                    }
-                }
-            }
-        }
-
-        Ok(targetable)
+        Ok(self.attackable(Coord::from(self.pos).within(range), board, players))
     }
 
     /// Returns a list of attackable coordinates at the specified range around the player
     pub fn adjacent_shell(&self, range: u32, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        for row in board.get_board()? {
-            for cell in row {
-                let distance = cell.get_pos()? - player_pos;
-                if distance.x.abs() + distance.y.abs() == range as f32 { //The cell is in range of the player
-                    if self.can_attack(cell.get_pos()?, board, players) { //The cell can be attacked
-                        targetable.push(cell.get_pos()?);
-                    }
-                }
-            }
-        }
-
-        Ok(targetable)
+        Ok(self.attackable(Coord::from(self.pos).ring(range), board, players))
     }
 
     /// Returns a list of attackable coordinates from the passed coordinate outwards
     pub fn radial_range(&self, location: Vector, radius: u32, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable = vec![];
-        let player_pos = location;
-
-        for row in board.get_board()? {
-            for cell in row {
-                let distance = cell.get_pos()? - player_pos;
-                if distance.x.abs() + distance.y.abs() <= radius as f32 { //The cell is in range of the player
-                    if self.can_attack(cell.get_pos()?, board, players) { //The cell can be attacked
-                        targetable.push(cell.get_pos()?);
-                    }
-                }
-            }
-        }
-
-        Ok(targetable)
+        Ok(self.attackable(Coord::from(location).within(radius), board, players))
     }
 
     /// Returns a list of attackable coordinates in the direction passed up to the range given
     pub fn directed_line_range(&self, range: u32, direction: Direction, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
         let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        //Cannot look off board
-        //We add 1 to `range` to make the call draw the correct range since we skip one element
-        match direction {
-            Direction::Up => {
-                if player_pos.y != 0.0 { //Top edge
-                    for elem in 1..range+1 {
-                        if player_pos.y - elem as f32 <= 0.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x, player_pos.y - elem as f32), board, players) {
-                            targetable.push(Vector::new(player_pos.x, player_pos.y - elem as f32));
-                        }
-                    }
-                }
-            },
-            Direction::Down => {
-                if player_pos.y != board.get_board()?.len() as f32 - 1.0 { //Bottom edge
-                    for elem in 1..range+1 {
-                        if player_pos.y + elem as f32 >= board.get_board()?.len() as f32 - 1.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x, player_pos.y + elem as f32), board, players) {
-                            targetable.push(Vector::new(player_pos.x, player_pos.y + elem as f32));
-                        }
-                    }
-                }
-            },
-            Direction::Left => {
-                if player_pos.x != 0.0 { //Left Edge
-                    for elem in 1..range+1 {
-                        if player_pos.x - elem as f32 <= 0.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x - elem as f32, player_pos.y), board, players) {
-                            targetable.push(Vector::new(player_pos.x - elem as f32, player_pos.y));
-                        }
-                    }
-                }
-            },
-            Direction::Right => {
-                if player_pos.x != board.get_board()?.first().unwrap().len() as f32 - 1.0 { //Right edge
-                    for elem in 1..range+1 {
-                        if player_pos.x + elem as f32 >= board.get_board()?.first().unwrap().len() as f32 - 1.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x + elem as f32, player_pos.y), board, players) {
-                            targetable.push(Vector::new(player_pos.x + elem as f32, player_pos.y));
-                        }
-                    }
-                }
-            },
+
+        for cell in self.line(range, direction, board) {
+            if self.can_attack(cell.into(), board, players) {
+                targetable.push(cell.into());
+            }
+            if self.blocks_line(cell, board)? { break } //Walls stop the line
         }
+        targetable.retain(|target| !self.concealed(*target, board, players));
 
         Ok(targetable)
     }
@@ -631,193 +690,97 @@ impl Entity {
     /// Returns a list of attackable coordinates in the direction passed up to the range given, stopping at an un-move-into-able obstacle
     pub fn directed_line_cast(&self, range: u32, direction: Direction, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
         let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        //Cannot look off board
-        //We add 1 to `range` to make the call draw the correct range since we skip one element
-        match direction {
-            Direction::Up => {
-                if player_pos.y != 0.0 { //Top edge
-                    for elem in 1..range+1 {
-                        if player_pos.y - elem as f32 <= 0.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x, player_pos.y - elem as f32), board, players) {
-                            targetable.push(Vector::new(player_pos.x, player_pos.y - elem as f32));
-
-                        } else {
-                            if self.can_move(Vector::new(player_pos.x, player_pos.y - elem as f32), board, players)? {
-                                targetable.push(Vector::new(player_pos.x, player_pos.y - elem as f32));
-                            }
-                            break;
-                        }
-                    }
-                }
-            },
-            Direction::Down => {
-                if player_pos.y != board.get_board()?.len() as f32 - 1.0 { //Bottom edge
-                    for elem in 1..range+1 {
-                        if player_pos.y + elem as f32 >= board.get_board()?.len() as f32 - 1.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x, player_pos.y + elem as f32), board, players) {
-                            targetable.push(Vector::new(player_pos.x, player_pos.y + elem as f32));
-                        } else {
-                            if self.can_move(Vector::new(player_pos.x, player_pos.y + elem as f32), board, players)? {
-                                targetable.push(Vector::new(player_pos.x, player_pos.y + elem as f32));
-                            }
-                            break;
-                        }
-                    }
-                }
-            },
-            Direction::Left => {
-                if player_pos.x != 0.0 { //Left Edge
-                    for elem in 1..range+1 {
-                        if player_pos.x - elem as f32 <= 0.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x - elem as f32, player_pos.y), board, players) {
-                            targetable.push(Vector::new(player_pos.x - elem as f32, player_pos.y));
-                        } else {
-                            if self.can_move(Vector::new(player_pos.x - elem as f32, player_pos.y), board, players)? {
-                                targetable.push(Vector::new(player_pos.x - elem as f32, player_pos.y));
-                            }
-                            break;
-                        }
-                    }
-                }
-            },
-            Direction::Right => {
-                if player_pos.x != board.get_board()?.first().unwrap().len() as f32 - 1.0 { //Right edge
-                    for elem in 1..range+1 {
-                        if player_pos.x + elem as f32 >= board.get_board()?.first().unwrap().len() as f32 - 1.0 { break } //Stop if we hit an edge
-                        if self.can_attack(Vector::new(player_pos.x + elem as f32, player_pos.y), board, players) {
-                            targetable.push(Vector::new(player_pos.x + elem as f32, player_pos.y));
-                        } else {
-                            if self.can_move(Vector::new(player_pos.x + elem as f32, player_pos.y), board, players)? {
-                                targetable.push(Vector::new(player_pos.x + elem as f32, player_pos.y));
-                            }
-                            break;
-                        }
-                    }
+
+        for cell in self.line(range, direction, board) {
+            let pos = Vector::from(cell);
+            if self.can_attack(pos, board, players) {
+                targetable.push(pos);
+                if self.blocks_line(cell, board)? { break; } //Walls stop the line
+            } else {
+                if self.can_move(pos, board, players)? {
+                    targetable.push(pos);
                 }
-            },
+                break;
+            }
         }
 
-        let mut counter = 0;
-        for target in &targetable {
-            if self.can_grapple(*target, board, players) { break; }
-            counter += 1;
+        targetable.retain(|target| !self.concealed(*target, board, players));
+
+        //The line ends at the first thing it can grab
+        if let Some(grapple) = targetable.iter().position(|target| self.can_grapple(*target, board, players)) {
+            targetable.truncate(grapple + 1);
         }
-        if counter == targetable.len(){ counter -= 1; }
-        targetable.split_off(counter+1);
 
         Ok(targetable)
     }
 
     /// Returns a list of attackable coordinates up to the direction passed at the radius given, stopping at an un-move-into-able obstacle
     pub fn directed_line_radial_cast(&self, range: u32, radius: u32, direction: Direction, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable = vec![];
-        let player_pos = self.pos;
-
-        //Cannot look off board
-        //We add 1 to `range` to make the call draw the correct range since we skip one element
-        match direction {
-            Direction::Up => {
-                if player_pos.y != 0.0 { //Top edge
-                    for elem in 1..range+1 {
-                        //Obstacle encountered, go back one element
-                        if !self.can_move(Vector::new(player_pos.x, player_pos.y - elem as f32), board, players)? {
-                            targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y - elem as f32 + 1.0), radius, board, players)?;
-                            break;
-                        }
-                        //Final iteration, no obstacles
-                        if elem == range {
-                            targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y - elem as f32), radius, board, players)?;
-                            break;
-                        }
-                    }
-                }
-            },
-            Direction::Down => {
-                if player_pos.y != board.get_board()?.len() as f32 - 1.0 { //Bottom edge
-                    for elem in 1..range+1 {
-                        //Obstacle encountered, go back one element
-                        if !self.can_move(Vector::new(player_pos.x, player_pos.y + elem as f32), board, players)? {
-                            targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y + elem as f32 - 1.0), radius, board, players)?;
-                            break;
-                        }
-                        //Final iteration, no obstacles
-                        if elem == range {
-                            targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y + elem as f32), radius, board, players)?;
-                            break;
-                        }
-
-                    }
-                }
-            },
-            Direction::Left => {
-                if player_pos.x != 0.0 { //Left Edge
-                    for elem in 1..range+1 {
-                        //Obstacle encountered, go back one element
-                        if !self.can_move(Vector::new(player_pos.x - elem as f32, player_pos.y), board, players)? {
-                            targetable = self.radial_range(Vector::new(player_pos.x - elem as f32 + 1.0, player_pos.y), radius, board, players)?;
-                            break;
-                        }
-                        //Final iteration, no obstacles
-                        if elem == range {
-                            targetable = self.radial_range(Vector::new(player_pos.x - elem as f32, player_pos.y), radius, board, players)?;
-                            break;
-                        }
-                    }
-                }
-            },
-            Direction::Right => {
-                if player_pos.x != board.get_board()?.first().unwrap().len() as f32 - 1.0 { //Right edge
-                    for elem in 1..range+1 {
-                        //Obstacle encountered, go back one element
-                        if !self.can_move(Vector::new(player_pos.x + elem as f32, player_pos.y), board, players)? {
-                            targetable = self.radial_range(Vector::new(player_pos.x + elem as f32 - 1.0, player_pos.y), radius, board, players)?;
-                            break;
-                        }
-                        //Final iteration, no obstacles
-                        if elem == range {
-                            targetable = self.radial_range(Vector::new(player_pos.x + elem as f32, player_pos.y), radius, board, players)?;
-                            break;
-                        }
-                    }
-                }
-            },
+        let origin = Coord::from(self.pos);
+        //Cannot throw off board
+        if range == 0 || !board.contains(origin.step(direction)) { return Ok(vec![]); }
+
+        //Lands at full range, or one element back from the first obstacle or board edge
+        let mut landing = origin;
+        for cell in origin.line(direction).take(range as usize) {
+            if !self.can_move(cell.into(), board, players)? { break; }
+            landing = cell;
         }
 
+        let mut targetable = self.radial_range(landing.into(), radius, board, players)?;
+        targetable.retain(|target| !self.concealed(*target, board, players));
+
         Ok(targetable)
     }
 
     /// Returns a list of attackable coordinates in the direction passed at the range given at the radius given. Using player will not be included
     pub fn directed_line_radial(&self, range: u32, radius: u32, direction: Direction, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
-        let mut targetable ;
-        let player_pos = self.pos;
-
-        //Cannot look off board
-        //We add 1 to `range` to make the call draw the correct range since we skip one element
-        match direction {
-            Direction::Up => {
-                targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y - range as f32), radius, board, players)?;
-            },
-            Direction::Down => {
-                targetable = self.radial_range(Vector::new(player_pos.x, player_pos.y + range as f32), radius, board, players)?;
-            },
-            Direction::Left => {
-                targetable = self.radial_range(Vector::new(player_pos.x - range as f32, player_pos.y), radius, board, players)?;
-            },
-            Direction::Right => {
-                targetable = self.radial_range(Vector::new(player_pos.x + range as f32, player_pos.y), radius, board, players)?;
-            },
-        }
+        let centre = Coord::from(self.pos).offset(direction, range as i32);
+        let mut targetable = self.radial_range(centre.into(), radius, board, players)?;
 
         //Caster is not included
-        if targetable.contains(&player_pos) {
-            let index =  targetable.iter().position(|x| *x == player_pos)
-                .expect("Cannot find index of existing element entities::directed_line_radial");
-            targetable.remove(index);
-        }
+        targetable.retain(|target| *target != self.pos && !self.concealed(*target, board, players));
 
         Ok(targetable)
     }
 
-}
\ No newline at end of file
+    /// Returns the cells `directed_line_radial` targets that can be hit from the air
+    pub fn directed_line_radial_sky(&self, range: u32, radius: u32, direction: Direction, board: &GameBoard, players: &Vec<Entity>) -> Result<Vec<Vector>> {
+        let mut targetable = self.directed_line_radial(range, radius, direction, board, players)?;
+        targetable.retain(|target| match board.get(Coord::from(*target)) {
+            Some(cell) => !cell.get_land().unwrap().rules().blocks_airraid,
+            None       => false,
+        });
+
+        Ok(targetable)
+    }
+
+    /// Returns the positions of the cells given that are on the board and can be attacked, in the order given
+    fn attackable(&self, cells: impl Iterator<Item = Coord>, board: &GameBoard, players: &Vec<Entity>) -> Vec<Vector> {
+        cells.map(Vector::from).filter(|pos| self.can_attack(*pos, board, players)).collect()
+    }
+
+    /// Returns up to `range` cells in a line from the entity
+    /// Lines stop short of the last cell of the board in their direction
+    fn line<'a>(&self, range: u32, direction: Direction, board: &'a GameBoard) -> impl Iterator<Item = Coord> + 'a {
+        Coord::from(self.pos).line(direction).take(range as usize)
+            .take_while(move |cell| board.contains(cell.step(direction)))
+    }
+
+    /// Returns true if the land at the passed location stops line abilities
+    fn blocks_line(&self, location: Coord, board: &GameBoard) -> Result<bool> {
+        Ok(board.cell(location)?.get_land()?.rules().blocks_lines)
+    }
+
+    /// Returns true if another living entity at the passed location is hidden from line abilities by the land
+    fn concealed(&self, location: Vector, board: &GameBoard, players: &[Entity]) -> bool {
+        let conceals = match board.get(Coord::from(location)) {
+            Some(cell) => cell.get_land().unwrap().rules().conceals,
+            None       => false,
+        };
+        if !conceals { return false; }
+
+        players.iter().any(|player| player.get_pos().unwrap() == location && location != self.pos && !player.is_dead())
+    }
+
+}
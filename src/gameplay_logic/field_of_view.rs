@@ -0,0 +1,111 @@
+/*
+Works out which cells can be seen for fog of war. Sight is found with recursive shadowcasting, which scans
+the eight octants around a viewer row by row and narrows the visible arc whenever sight is blocked.
+See http://www.roguebasin.com/index.php/FOV_using_recursive_shadowcasting
+*/
+
+use crate::gameplay_logic::coord::Coord;
+use crate::gameplay_logic::entities::{Entity, PlayerType};
+use crate::gameplay_logic::game_board::GameBoard;
+
+use quicksilver::prelude::*;
+
+//Multipliers that map the first octant onto each of the eight
+const OCTANTS: [[i32; 4]; 8] = [
+    [ 1,  0,  0,  1], [ 0,  1,  1,  0], [ 0, -1,  1,  0], [-1,  0,  0,  1],
+    [-1,  0,  0, -1], [ 0, -1, -1,  0], [ 0,  1, -1,  0], [ 1,  0,  0, -1],
+];
+
+/// Returns which cells can be seen from `origin` up to `radius` cells away, indexed `[y][x]`
+/// Blocking cells can be seen but nothing behind them can
+pub fn visible_from(board: &GameBoard, origin: Vector, radius: u32) -> Result<Vec<Vec<bool>>> {
+    let mut blocking = vec![vec![false; board.width()]; board.height()];
+    for coord in board.coords() {
+        blocking[coord.y as usize][coord.x as usize] = board.cell(coord)?.get_land()?.rules().blocks_sight;
+    }
+
+    let mut visible = vec![vec![false; board.width()]; board.height()];
+    let origin = Coord::from(origin);
+    if !board.contains(origin) { return Ok(visible); }
+    visible[origin.y as usize][origin.x as usize] = true;
+
+    for octant in OCTANTS.iter() {
+        cast_light(&blocking, &mut visible, (origin.x, origin.y), radius as i32, 1, 1.0, 0.0, octant);
+    }
+
+    Ok(visible)
+}
+
+/// Returns which cells any living member of `team` can see, indexed `[y][x]`
+pub fn team_vision(board: &GameBoard, players: &[Entity], team: PlayerType) -> Result<Vec<Vec<bool>>> {
+    let mut vision: Vec<Vec<bool>> = vec![];
+    for player in players {
+        if *player.get_player()? != team || player.is_dead() { continue; }
+
+        let seen = visible_from(board, player.get_pos()?, player.get_class()?.vision())?;
+        if vision.is_empty() {
+            vision = seen;
+        } else {
+            for (row, seen_row) in vision.iter_mut().zip(seen.iter()) {
+                for (cell, seen_cell) in row.iter_mut().zip(seen_row.iter()) {
+                    *cell = *cell || *seen_cell;
+                }
+            }
+        }
+    }
+
+    if vision.is_empty() { //A team with nobody left sees nothing
+        vision = vec![vec![false; board.width()]; board.height()];
+    }
+    Ok(vision)
+}
+
+/// Returns true if the cell is seen in a vision map from `visible_from` or `team_vision`, cells off the board never are
+pub fn sees(vision: &[Vec<bool>], coord: Coord) -> bool {
+    if coord.x < 0 || coord.y < 0 { return false; }
+    vision.get(coord.y as usize).and_then(|row| row.get(coord.x as usize)).copied().unwrap_or(false)
+}
+
+/// Lights one octant from `row` outwards between the `start` and `end` slopes, recursing around blocking cells
+#[allow(clippy::too_many_arguments)]
+fn cast_light(blocking: &[Vec<bool>], visible: &mut [Vec<bool>], origin: (i32, i32), radius: i32,
+              row: i32, mut start: f32, end: f32, octant: &[i32; 4]) {
+    if start < end { return; }
+    let [xx, xy, yx, yy] = *octant;
+    let mut new_start = 0.0;
+
+    for distance in row..=radius {
+        let dy = -distance;
+        let mut blocked = false;
+
+        for dx in -distance..=0 {
+            let x = origin.0 + dx * xx + dy * xy;
+            let y = origin.1 + dx * yx + dy * yy;
+            let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+            if start < right_slope { continue; }
+            if end > left_slope { break; }
+
+            let on_board = x >= 0 && y >= 0 && (y as usize) < blocking.len() && (x as usize) < blocking[y as usize].len();
+            if on_board && dx * dx + dy * dy <= radius * radius {
+                visible[y as usize][x as usize] = true;
+            }
+
+            let opaque = !on_board || blocking[y as usize][x as usize];
+            if blocked {
+                if opaque {
+                    new_start = right_slope;
+                } else {
+                    blocked = false;
+                    start = new_start;
+                }
+            } else if opaque && distance < radius {
+                blocked = true;
+                cast_light(blocking, visible, origin, radius, distance + 1, start, left_slope, octant);
+                new_start = right_slope;
+            }
+        }
+
+        if blocked { break; }
+    }
+}
@@ -3,8 +3,19 @@ This is the environment layer, which models the board and its contents
 */
 
 use quicksilver::prelude::*;
+use crate::gameplay_logic::coord::Coord;
 use crate::gameplay_logic::gameplay_type::{Terrain, TerrainStatus, to_condition, to_terrain};
 use crate::gameplay_logic::game_levels::Levels;
+use crate::gameplay_logic::game_match::MatchRng;
+use crate::gameplay_logic::snapshot::Snapshot;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use std::io;
+
+//Environment step tuning
+const SPREAD_BURN_TURNS: u32 = 3; //Turns a cell burns for after fire spreads into it
+const REGROW_CHANCE: f64 = 0.1; //Chance each round a Destroyed cell grows back into Plain
+const COLLAPSE_CHANCE: f64 = 0.25; //Chance each round a Destroyed cell next to a Pit collapses into one
 
 /// Generation logic adapted from [Quicksilver Rougelike](https://github.com/tomassedovic/quicksilver-roguelike)
 /// Generates a map with the initial game state
@@ -47,7 +58,7 @@ fn generate_map(size: Vector, level: Vec<String>, level_condition: Vec<String>)
 /// Cells are the atomic elements that describe what a unit consists of.
 /// It holds a position Vector to model. Some conditions are considered temporary in game and
 /// will decrement and reset over time
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Cell {
     pos: Vector,
     land: Terrain,
@@ -77,7 +88,7 @@ impl Cell {
     pub fn decr_counter(&mut self) {
         if self.cond_counter > 0{
             self.cond_counter -= 1;
-            if self.cond_counter <= 0 {
+            if self.cond_counter == 0 {
                 self.condition = TerrainStatus::Normal;
             }
         }
@@ -105,16 +116,17 @@ impl Cell {
 }
 
 /// The GameBoard is the environment that contains the game data
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameBoard {
     //Environment
     board: Vec<Vec<Cell>>,
 }
 
 impl GameBoard {
-    /// Initializes the game state
-    pub fn new() -> Result<Self> {
-        let level_set = Levels::new().expect("Cannot initialize levels").
-            get_level(1).expect("Cannot load level 1");
+    /// Initializes the game state with a level from `Levels`, or an error if there is no such level
+    pub fn new(level: usize) -> Result<Self> {
+        let level_set = Levels::new()?.get_level(level)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("There is no level {}", level)))?;
         let gameboard = generate_map(Vector::new(19.0, 15.0), level_set.0, level_set.1);
 
         Ok(Self {
@@ -122,11 +134,75 @@ impl GameBoard {
         })
     }
 
-    pub fn get_board(&self) -> Result<&Vec<Vec<Cell>>> {
-        Ok(&self.board)
+    /// Resets every starting condition so the level begins as bare land, impassable space is kept
+    pub fn clear_conditions(&mut self) -> Result<()> {
+        for row in &mut self.board {
+            for cell in row {
+                if *cell.get_cond()? != TerrainStatus::Impassable {
+                    cell.reset_cond();
+                }
+            }
+        }
+
+        Ok(())
     }
-    pub fn get_mut_board(&mut self) -> Result<&mut Vec<Vec<Cell>>> {
-        Ok(&mut self.board)
+
+    /// Returns a stable text record of every cell, see `Snapshot`
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        Snapshot::of_board(self)
+    }
+
+    /// Returns the cell at a position, or None if the position is off the board
+    pub fn get(&self, coord: Coord) -> Option<&Cell> {
+        if coord.x < 0 || coord.y < 0 { return None; }
+        self.board.get(coord.y as usize)?.get(coord.x as usize)
+    }
+
+    /// Returns the cell at a position to change, or None if the position is off the board
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut Cell> {
+        if coord.x < 0 || coord.y < 0 { return None; }
+        self.board.get_mut(coord.y as usize)?.get_mut(coord.x as usize)
+    }
+
+    /// Returns the cell at a position the rules expect to be on the board, a position off it is an error
+    pub fn cell(&self, coord: Coord) -> Result<&Cell> {
+        self.get(coord).ok_or_else(|| GameBoard::off_board(coord))
+    }
+
+    /// Returns the cell at a position the rules expect to be on the board to change, a position off it is an error
+    pub fn cell_mut(&mut self, coord: Coord) -> Result<&mut Cell> {
+        self.get_mut(coord).ok_or_else(|| GameBoard::off_board(coord))
+    }
+
+    /// Returns true if the position is a cell of the board
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.get(coord).is_some()
+    }
+
+    /// Returns the position of every cell, row by row from the top
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.board.iter().enumerate()
+            .flat_map(|(y, row)| (0..row.len()).map(move |x| Coord::new(x as i32, y as i32)))
+    }
+
+    /// Returns every cell, row by row from the top
+    pub fn cells(&self) -> impl Iterator<Item = &Cell> + '_ {
+        self.board.iter().flatten()
+    }
+
+    /// Returns every cell to change, row by row from the top
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut Cell> + '_ {
+        self.board.iter_mut().flatten()
+    }
+
+    /// Returns the number of cells in a row
+    pub fn width(&self) -> usize {
+        self.board.first().map_or(0, |row| row.len())
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> usize {
+        self.board.len()
     }
 
     ///Decrements some counters in cell, and resets conditions to Normal if it reaches 0
@@ -137,13 +213,13 @@ impl GameBoard {
                 match cell.get_cond()? {
                     TerrainStatus::Burning    => {
                         if cell.get_counter()? > 0 { cell.set_counter(cell.get_counter()? - 1); }
-                        if cell.get_counter()? <= 0 {
+                        if cell.get_counter()? == 0 {
                             cell.set_cond(TerrainStatus::Normal);
                             cell.set_land(Terrain::Destroyed)}
                     },
                     TerrainStatus::Frozen     => {
                         if cell.get_counter()? > 0 { cell.set_counter(cell.get_counter()? - 1); }
-                        if cell.get_counter()? <= 0 { cell.set_cond(TerrainStatus::Normal); }
+                        if cell.get_counter()? == 0 { cell.set_cond(TerrainStatus::Normal); }
                     },
                     _                         => {}
                 }
@@ -152,4 +228,51 @@ impl GameBoard {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Advances the environment by one round. Every change is decided from the board as it was at the start of the step:
+    /// Fire spreads into neighbouring flammable land, frozen cells put out neighbouring fire, destroyed land
+    /// regrows into plain and collapses into neighbouring pits
+    /// The same seed and round always give the same result, the draws come from a `MatchRng` seeded with both
+    pub fn environment_step(&mut self, seed: u64, round: u32) -> Result<()> {
+        let mut rng = MatchRng::new(seed ^ (round as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let before = self.clone();
+
+        for coord in before.coords() {
+            let cell = before.cell(coord)?;
+            let land = *cell.get_land()?;
+            let cond = *cell.get_cond()?;
+            if cond == TerrainStatus::Impassable || land == Terrain::Empty { continue; }
+
+            let neighbours: Vec<Cell> = coord.neighbours().filter_map(|next| before.get(next)).copied().collect();
+            let next_to = |status: TerrainStatus| neighbours.iter().any(|n| n.condition == status);
+            let target = self.cell_mut(coord)?;
+
+            match cond {
+                TerrainStatus::Burning if next_to(TerrainStatus::Frozen) => target.reset_cond(),
+                TerrainStatus::Normal => {
+                    //Every burning neighbour gets a chance to light the cell, unless ice next to it puts the fire out
+                    let burning = neighbours.iter().filter(|n| n.condition == TerrainStatus::Burning).count();
+                    let flammability = land.rules().flammability;
+                    let caught = (0..burning).any(|_| rng.gen_bool(flammability));
+                    if caught && !next_to(TerrainStatus::Frozen) {
+                        target.cond_with_counter(TerrainStatus::Burning, SPREAD_BURN_TURNS);
+                    } else if land == Terrain::Destroyed {
+                        if neighbours.iter().any(|n| n.land == Terrain::Pit) && rng.gen_bool(COLLAPSE_CHANCE) {
+                            target.set_land(Terrain::Pit);
+                        } else if rng.gen_bool(REGROW_CHANCE) {
+                            target.set_land(Terrain::Plain);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Error for a position the rules expected to be on the board
+    fn off_board(coord: Coord) -> Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("Cell {},{} is off the board", coord.x, coord.y)).into()
+    }
+}
@@ -2,7 +2,10 @@
 This is a struct to hold level vectors to avoid complicated rendering logic
 */
 
-use quicksilver::prelude::Result;
+use quicksilver::prelude::{Result, Vector};
+
+/// Number of levels, they are numbered from 1
+pub const LEVEL_COUNT: usize = 2;
 
 ///Defines a set of "-"9x"-"5 levels, by convention only the circle within the center "-"3x"-"3 square are
 /// changed to anything besides the null space
@@ -10,6 +13,8 @@ use quicksilver::prelude::Result;
 pub struct Levels {
     level1: Vec<String>,
     level1_conditions: Vec<String>,
+    level2: Vec<String>,
+    level2_conditions: Vec<String>,
 }
 
 /// Defines a macro to make a Vec of String
@@ -59,18 +64,76 @@ impl Levels {
                 "-", "-", "-", "-", "-", "-", "-", "F", "F", "F", "F", "F", "-", "-", "-", "-", "-", "-", "-",
                 "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-"
             ],
+            level2: vec_of_strings![
+                "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "#", "#", "R", "#", "#", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "#", "#", "F", "F", "R", "F", "F", "#", "#", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "#", "F", "#", "#", "R", "R", "R", "#", "#", "F", "#", "-", "-", "-", "-",
+                "-", "-", "-", "-", "#", "#", "C", "W", "W", "#", "W", "W", "C", "#", "#", "-", "-", "-", "-",
+                "-", "-", "-", "#", "#", "C", "C", "#", "X", "#", "X", "#", "C", "C", "#", "#", "-", "-", "-",
+                "-", "-", "-", "#", "W", "#", "C", "#", "X", "X", "X", "#", "C", "#", "W", "#", "-", "-", "-",
+                "-", "-", "-", "#", "W", "#", "#", "X", "X", "O", "X", "X", "#", "#", "W", "#", "-", "-", "-",
+                "-", "-", "-", "#", "W", "#", "C", "#", "X", "X", "X", "#", "C", "#", "W", "#", "-", "-", "-",
+                "-", "-", "-", "#", "#", "C", "C", "#", "X", "#", "X", "#", "C", "C", "#", "#", "-", "-", "-",
+                "-", "-", "-", "-", "#", "#", "C", "W", "W", "#", "W", "W", "C", "#", "#", "-", "-", "-", "-",
+                "-", "-", "-", "-", "#", "A", "#", "#", "R", "R", "R", "#", "#", "A", "#", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "A", "A", "#", "#", "R", "#", "#", "A", "A", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "A", "A", "#", "A", "A", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-"
+            ],
+            level2_conditions: vec_of_strings![
+                "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "N", "N", "N", "N", "N", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "N", "N", "F", "F", "N", "F", "F", "N", "N", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "N", "N", "N", "N", "N", "N", "N", "N", "N", "N", "N", "-", "-", "-", "-",
+                "-", "-", "-", "-", "N", "N", "N", "-", "-", "N", "-", "-", "N", "N", "N", "-", "-", "-", "-",
+                "-", "-", "-", "N", "N", "N", "N", "N", "B", "N", "N", "N", "N", "N", "N", "N", "-", "-", "-",
+                "-", "-", "-", "N", "-", "N", "S", "N", "N", "N", "N", "N", "N", "N", "-", "N", "-", "-", "-",
+                "-", "-", "-", "N", "-", "N", "N", "N", "N", "-", "N", "N", "N", "N", "-", "N", "-", "-", "-",
+                "-", "-", "-", "N", "-", "N", "N", "N", "N", "N", "N", "N", "S", "N", "-", "N", "-", "-", "-",
+                "-", "-", "-", "N", "N", "N", "N", "N", "N", "N", "B", "N", "N", "N", "N", "N", "-", "-", "-",
+                "-", "-", "-", "-", "N", "N", "N", "-", "-", "N", "-", "-", "N", "N", "N", "-", "-", "-", "-",
+                "-", "-", "-", "-", "N", "N", "N", "N", "N", "N", "N", "N", "N", "N", "N", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "N", "N", "N", "N", "N", "N", "N", "N", "N", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "N", "N", "N", "N", "N", "-", "-", "-", "-", "-", "-", "-",
+                "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-", "-"
+            ],
         })
     }
 
     ///Return a tuple clone of level icon data and condition data in
     /// the format below. Both tuples are Vec<String>. If not found returns None.
     ///
-    /// ```
+    /// ```text
     /// Some((level_data, level_data_conditions))
     /// ```
     pub fn get_level(&self, level: usize) -> Option<(Vec<String>, Vec<String>)> {
         match level {
             1 => Some((self.level1.clone(), self.level1_conditions.clone())),
+            2 => Some((self.level2.clone(), self.level2_conditions.clone())),
+            _ => None,
+        }
+    }
+
+    ///Return the name of a level shown when setting up a match. If not found returns None.
+    pub fn get_name(&self, level: usize) -> Option<&str> {
+        match level {
+            1 => Some("Crossroads"),
+            2 => Some("Frozen Ruins"),
+            _ => None,
+        }
+    }
+
+    ///Return the starting positions of a level in the format below, hunters are in Support, Assault, Trapper order.
+    /// If not found returns None.
+    ///
+    /// ```text
+    /// Some((monster_position, [support_position, assault_position, trapper_position]))
+    /// ```
+    pub fn get_spawns(&self, level: usize) -> Option<(Vector, [Vector; 3])> {
+        match level {
+            1 => Some((Vector::new(9, 11), [Vector::new(6, 4), Vector::new(9, 3), Vector::new(12, 4)])),
+            2 => Some((Vector::new(9, 12), [Vector::new(6, 3), Vector::new(9, 2), Vector::new(12, 3)])),
             _ => None,
         }
     }
@@ -0,0 +1,766 @@
+/*
+The rules of a match with no graphics, sound or input attached. A match only changes through the commands
+players give it, so the same match can be played on screen, hosted for other machines or run on a server.
+Everything random is drawn from the match's own generator, which is saved along with it, or for the environment
+from a generator of the same kind seeded with the match's seed and the round. A match given the same setup and
+the same commands always plays out the same.
+*/
+
+use crate::gameplay_logic::coord::Coord;
+use crate::gameplay_logic::entities::*;
+use crate::gameplay_logic::field_of_view;
+use crate::gameplay_logic::game_board::GameBoard;
+use crate::gameplay_logic::gameplay_type::{Direction, Terrain, TerrainStatus, FULL_MOVE};
+use crate::gameplay_logic::match_config::MatchConfig;
+use crate::gameplay_logic::snapshot::Snapshot;
+use crate::gameplay_logic::turn_order::TurnOrder;
+
+use quicksilver::prelude::*;
+use rand::{Rng, RngCore};
+use serde::{Serialize, Deserialize};
+
+/// Monster turns a sign stays on the board
+pub const SIGN_TURNS: u32 = 2;
+/// Where a view of the match puts the entities its team cannot see, off the board so nothing finds them
+const UNSEEN: Vector = Vector { x: -1.0, y: -1.0 };
+
+/// Marks the monster leaves for the hunters in handoff mode
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Sign {
+    Track, //The monster moved through here
+    Noise, //The monster used an ability here
+}
+
+/// Everything a player can ask of a match
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Command {
+    Move(Direction),
+    Act(u32, Direction), //Ability 1-3 of the current player, aimed in a direction
+    EndTurn,
+    Concede(PlayerType), //The team giving up
+}
+
+/// What happened while a command was carried out, so it can be shown and heard
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum MatchEvent {
+    Moved { entity: usize, from: Vector, to: Vector },
+    Teleported { entity: usize, from: Vector, to: Vector },
+    Hazard { entity: usize }, //Stepped onto hurtful land
+    Ability { ability: ActionAbility, caster: Vector, affected: Vec<Vector> },
+    TurnChanged { from: PlayerType, to: PlayerType },
+}
+
+/// A small seeded random number generator (SplitMix64) that can be saved and sent with the match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRng {
+    state: u64,
+}
+
+impl MatchRng {
+    pub fn new(seed: u64) -> Self { Self { state: seed } }
+}
+
+impl RngCore for MatchRng {
+    fn next_u32(&mut self) -> u32 { (self.next_u64() >> 32) as u32 }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The state of a match and the rules that change it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Match {
+    config: MatchConfig,
+    board: GameBoard,
+    entities: Vec<Entity>,
+    turn_order: TurnOrder,
+    current: usize, //Index of the entity whose turn it is
+    moves: u32, //Half moves left, see `FULL_MOVE`
+    actions: u32,
+    turn_start: Vector, //Where the current entity began its turn
+    signs: Vec<(Vector, Sign, u32)>, //Where the monster has been, and for how many more of its turns it shows
+    winner: PlayerType,
+    rng: MatchRng,
+    #[serde(skip)]
+    events: Vec<MatchEvent>,
+}
+
+impl Match {
+    /// Starts a match set up with `config`, or an error if `MatchConfig::check` refuses it
+    pub fn new(config: MatchConfig) -> Result<Self> {
+        config.check()?;
+        Match::with_setup(config, config.create_board()?, config.create_entities()?)
+    }
+
+    /// Starts a match on a prepared board with prepared entities instead of the level's, for tests and tools
+    /// The rest of the rules still come from `config`
+    pub fn with_setup(config: MatchConfig, board: GameBoard, entities: Vec<Entity>) -> Result<Self> {
+        let mut turn_order = TurnOrder::new(config.turn_scheme);
+        let current = turn_order.next(&entities)?.expect("Cannot find first player");
+
+        let mut new_match = Self {
+            config, board, turn_order, current,
+            moves: *entities[current].get_stats()?.get_speed() as u32 * FULL_MOVE,
+            actions: *entities[current].get_stats()?.get_actions() as u32,
+            turn_start: entities[current].get_pos()?,
+            entities,
+            signs: vec![],
+            winner: PlayerType::Undetermined,
+            rng: MatchRng::new(config.seed),
+            events: vec![],
+        };
+        new_match.update_cover()?;
+
+        Ok(new_match)
+    }
+
+    pub fn config(&self) -> &MatchConfig { &self.config }
+    pub fn board(&self) -> &GameBoard { &self.board }
+    pub fn entities(&self) -> &Vec<Entity> { &self.entities }
+    pub fn current(&self) -> usize { self.current }
+    pub fn current_entity(&self) -> &Entity { &self.entities[self.current] }
+    pub fn moves(&self) -> u32 { self.moves }
+    pub fn actions(&self) -> u32 { self.actions }
+    pub fn round(&self) -> u32 { self.turn_order.round() }
+    pub fn signs(&self) -> &[(Vector, Sign, u32)] { &self.signs }
+    pub fn winner(&self) -> PlayerType { self.winner }
+
+    /// Returns a stable text record of the match, see `Snapshot`
+    pub fn snapshot(&self) -> Result<Snapshot> { Snapshot::of_match(self) }
+
+    /// Returns the team whose turn it is
+    pub fn team(&self) -> Result<PlayerType> { Ok(*self.entities[self.current].get_player()?) }
+
+    /// Returns and clears what has happened since the last call
+    pub fn drain_events(&mut self) -> Vec<MatchEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Returns true if the monster is close to defeat
+    pub fn is_tense(&mut self) -> Result<bool> {
+        for player in &mut self.entities {
+            if *player.get_player()? == PlayerType::Player1 {
+                let max_hp = *player.get_stats()?.get_hp();
+                if *player.get_curr_stats()?.get_hp() < max_hp * 0.3 { return Ok(true); }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns which cells `team` can see in fog of war, indexed `[y][x]`, or None when everything is in sight
+    pub fn vision(&self, team: PlayerType) -> Result<Option<Vec<Vec<bool>>>> {
+        if !self.config.fog_of_war { return Ok(None); }
+        Ok(Some(field_of_view::team_vision(&self.board, &self.entities, team)?))
+    }
+
+    /// Returns true if the entity is the monster moving unseen in handoff mode, it is only given away by hunters next to it
+    pub fn unheard(&self, entity: &Entity) -> Result<bool> {
+        if !self.config.handoff || *entity.get_player()? != PlayerType::Player1 { return Ok(false); }
+
+        let pos = entity.get_pos()?;
+        for player in self.entities.iter() {
+            if *player.get_player()? != PlayerType::Player2 || player.is_dead() { continue; }
+            let distance = player.get_pos()? - pos;
+            if distance.x.abs() + distance.y.abs() <= 1.0 { return Ok(false); }
+        }
+        Ok(true)
+    }
+
+    /// Returns true if `team`, seeing the cells in `vision`, cannot see the entity
+    /// Invisible entities, those outside the team's sight and the monster moving unheard are hidden from the other team only
+    pub fn hidden_from(&self, entity: &Entity, team: PlayerType, vision: Option<&[Vec<bool>]>) -> Result<bool> {
+        if *entity.get_player()? == team { return Ok(false); }
+        let pos = Coord::from(entity.get_pos()?);
+        let seen = vision.is_none_or(|vision| field_of_view::sees(vision, pos));
+        Ok(entity.has_effect(StatusEffect::Invisible) || !seen || self.unheard(entity)?)
+    }
+
+    /// Returns the match and what just happened as `team` may see them, for sending to another machine
+    /// Entities the team cannot see are moved off the board and what happened to them is left out, and the random
+    /// generator and seed are cleared so rolls cannot be foreseen. Spectators pass None and see only what both teams can
+    pub fn view_for(&self, team: Option<PlayerType>, events: &[MatchEvent]) -> Result<(Match, Vec<MatchEvent>)> {
+        let visions = [self.vision(PlayerType::Player1)?, self.vision(PlayerType::Player2)?];
+        let mut hidden = vec![];
+        for (index, entity) in self.entities.iter().enumerate() {
+            let viewer = match team {
+                Some(team) => team,
+                None if *entity.get_player()? == PlayerType::Player1 => PlayerType::Player2,
+                None => PlayerType::Player1,
+            };
+            let vision = visions[if viewer == PlayerType::Player1 { 0 } else { 1 }].as_deref();
+            if self.hidden_from(entity, viewer, vision)? { hidden.push(index); }
+        }
+
+        let mut view = self.clone();
+        view.rng = MatchRng::new(0);
+        view.config.seed = 0;
+        view.events.clear();
+        for index in hidden.iter() {
+            view.entities[*index].set_pos(UNSEEN)?;
+            if *index == self.current { view.turn_start = UNSEEN; }
+        }
+
+        //Anything done at a hidden entity's cell would give it away
+        let mut hidden_cells = vec![];
+        for index in hidden.iter() { hidden_cells.push(self.entities[*index].get_pos()?); }
+        let events = events.iter().filter(|event| match event {
+            MatchEvent::Moved { entity, from, to } | MatchEvent::Teleported { entity, from, to } =>
+                !hidden.contains(entity) && !hidden_cells.contains(from) && !hidden_cells.contains(to),
+            MatchEvent::Hazard { entity }            => !hidden.contains(entity),
+            MatchEvent::Ability { caster, .. }       => !hidden_cells.contains(caster),
+            MatchEvent::TurnChanged { .. }           => true,
+        }).cloned().collect();
+
+        Ok((view, events))
+    }
+
+    /// Carries out a command for the current player if the rules allow it, returns false if it was refused
+    /// Nothing can be done once the match has a winner
+    pub fn apply(&mut self, command: Command) -> Result<bool> {
+        if self.winner != PlayerType::Undetermined { return Ok(false); }
+
+        let applied = match command {
+            Command::Move(direction) => {
+                let new_loc = Coord::from(self.entities[self.current].get_pos()?).step(direction);
+                self.moves > 0 && self.board.contains(new_loc) && self.try_move(new_loc.into())?
+            },
+            Command::Act(ability, direction) => {
+                //Check to see if a player is allowed to use the selected ability and use it if so
+                let allowed = self.actions > 0 && self.entities[self.current].can_act(ability, &self.board, &self.entities)?;
+                if allowed {
+                    let (targets, ability_name) = self.entities[self.current].act(ability, direction, &self.board, &self.entities)?;
+                    self.execute_action(targets, ability_name)?;
+                    self.actions -= 1;
+                }
+                allowed
+            },
+            Command::EndTurn => {
+                self.next_turn()?;
+                true
+            },
+            Command::Concede(team) => {
+                self.winner = match team {
+                    PlayerType::Player1 => PlayerType::Player2,
+                    PlayerType::Player2 => PlayerType::Player1,
+                    _                   => PlayerType::Undetermined,
+                };
+                self.winner != PlayerType::Undetermined
+            },
+        };
+
+        if applied && self.winner == PlayerType::Undetermined {
+            //Hunters at 0 hp go down, a hunter downed on their own turn loses the rest of it
+            self.down_hunters()?;
+            if self.entities[self.current].get_status()? == Status::Downed {
+                self.next_turn()?;
+            }
+            self.check_game()?;
+        }
+
+        Ok(applied)
+    }
+
+    /// Shifts index to the next player's turn, and sets variables appropriately
+    /// Also apply buff and debuff affects based on player status effects and land condition
+    fn next_turn(&mut self) -> Result<()> {
+        let last_round = self.turn_order.round();
+        let last_team = self.team()?;
+        self.current = self.turn_order.next(&self.entities)?.expect("Cannot find next player index game_match::next_turn");
+        //Downed hunters cannot act, their turn passes and brings them closer to bleeding out
+        while self.entities[self.current].get_status()? == Status::Downed {
+            self.entities[self.current].decrement_timer();
+            self.current = self.turn_order.next(&self.entities)?.expect("Cannot find next player index game_match::next_turn");
+        }
+
+        //The environment moves on once per round
+        for round in last_round + 1..=self.turn_order.round() {
+            self.board.environment_step(self.config.seed, round)?;
+        }
+
+        //The monster's signs fade with its turns
+        let team = self.team()?;
+        if team == PlayerType::Player1 {
+            for sign in self.signs.iter_mut() { sign.2 -= 1; }
+            self.signs.retain(|sign| sign.2 > 0);
+        }
+        self.events.push(MatchEvent::TurnChanged { from: last_team, to: team });
+
+        //Status effects tick and set this turn's stats
+        self.entities[self.current].tick_effects()?;
+
+        self.moves = *self.entities[self.current].get_curr_stats()?.get_speed() as u32 * FULL_MOVE;
+        self.actions = *self.entities[self.current].get_curr_stats()?.get_actions() as u32;
+
+        self.turn_start = self.entities[self.current].get_pos()?;
+        self.update_cover()?; //Abilities may have moved players or changed the land
+
+        let starting_cell = self.board.cell(Coord::from(self.turn_start))?;
+        //Set buffs and debuffs depending on land status
+        let starting_cond = starting_cell.get_cond()?;
+        match starting_cond {
+            TerrainStatus::Burning   => { //Damage players on a burning tile
+                let lvl = self.entities[self.current].get_level()? as f32;
+                let dmg_pow = lvl * 5.0 + self.rng.gen_range(0.0, lvl);
+
+                self.entities[self.current].add_checked_hp(-dmg_pow)?;
+            },
+            TerrainStatus::Frozen    => { self.moves = 0; }, //Can't move if frozen
+            _                        => {/*do nothing*/},
+        }
+
+        //Decrement status buff/debuff timer and gameboard land cond timers.
+        self.entities[self.current].decrement_timer();
+        self.board.decrement_temp_cond_counters()?;
+
+        Ok(())
+    }
+
+    /// Checks for victory conditions and sets the winner if the match is over
+    /// The hunters win when the monster runs out of hp, the monster wins once every hunter is dead
+    fn check_game(&mut self) -> Result<()> {
+        let mut monster_alive = false;
+        let mut hunters_alive = false;
+
+        for player in &mut self.entities {
+            match player.get_player()? {
+                PlayerType::Player1 => monster_alive |= *player.get_curr_stats()?.get_hp() > 0.0,
+                PlayerType::Player2 => hunters_alive |= !player.is_dead(),
+                _                   => {/*Do nothing*/},
+            }
+        }
+
+        if !monster_alive {
+            self.winner = PlayerType::Player2;
+        } else if !hunters_alive {
+            self.winner = PlayerType::Player1;
+        }
+
+        //The Elder God wins by outlasting the round limit
+        let round = self.turn_order.round();
+        if self.winner == PlayerType::Undetermined && matches!(self.config.round_limit, Some(limit) if round > limit) {
+            self.winner = PlayerType::Player1;
+        }
+
+        Ok(())
+    }
+
+    /// Downs every hunter that has run out of hp
+    fn down_hunters(&mut self) -> Result<()> {
+        for player in &mut self.entities {
+            player.knock_down()?;
+        }
+        Ok(())
+    }
+
+    /// Tries to move a player, returns true if moved, false otherwise
+    /// Entering a cell spends moves by its terrain's rules, and hazardous terrain damages or stops the player
+    /// Terrain damage scales with level and armor reduces damage.
+    fn try_move(&mut self, new_loc: Vector) -> Result<bool> {
+        let mut retval= false;
+
+        if self.entities[self.current].can_move(new_loc, &self.board, &self.entities)? {
+            let rules = self.board.cell(Coord::from(new_loc))?.get_land()?.rules();
+            if !rules.can_enter(self.moves) { return Ok(false); }
+
+            let old_loc = self.entities[self.current].get_pos()?;
+            self.entities[self.current].set_pos(new_loc)?;
+            self.events.push(MatchEvent::Moved { entity: self.current, from: old_loc, to: new_loc });
+            self.moves = rules.moves_after(self.moves);
+            self.update_cover()?;
+            self.leave_sign(old_loc, Sign::Track)?;
+
+            //Spikes and pits are bad for you, don't touch them
+            if rules.damage > 0.0 {
+                self.events.push(MatchEvent::Hazard { entity: self.current });
+                let damage = self.entities[self.current].get_level()? as f32 * rules.damage;
+                let total_dmg = self.entities[self.current].get_curr_stats()?.armor_reduce(damage);
+                self.entities[self.current].add_checked_hp(-total_dmg)?;
+            }
+
+            retval = true;
+        }
+
+        Ok(retval)
+    }
+
+    /// Marks a cell the monster moved through or made noise on, so the hunters can follow it in handoff mode
+    fn leave_sign(&mut self, location: Vector, sign: Sign) -> Result<()> {
+        if self.config.handoff && self.team()? == PlayerType::Player1 {
+            self.signs.retain(|(pos, _, _)| *pos != location);
+            self.signs.push((location, sign, SIGN_TURNS));
+        }
+        Ok(())
+    }
+
+    /// Gives every player the armor of the land they stand on
+    fn update_cover(&mut self) -> Result<()> {
+        for player in &mut self.entities {
+            let pos = player.get_pos()?;
+            let land = *self.board.cell(Coord::from(pos))?.get_land()?;
+            player.set_cover(land.rules().armor_bonus)?;
+        }
+        Ok(())
+    }
+}
+
+/// This impl contains Action definitions and a routing function to execute them
+/// Targets come from the entity's targeting, a target off the board is reported as an error rather than indexed
+/// We also assume that passed coordinates are the correct targets and do not check that either
+impl Match {
+    /// Executes passed action on the targets passed
+    fn execute_action(&mut self, targets: Vec<Vector>, ability_name: ActionAbility) -> Result<()> {
+        let caster = self.entities[self.current].get_pos()?;
+        let mut affected = targets.clone();
+
+        //Acting gives away an invisible caster, Decoy is how the monster turns invisible
+        self.entities[self.current].remove_effect(StatusEffect::Invisible)?;
+        self.update_cover()?;
+        self.leave_sign(caster, Sign::Noise)?;
+
+        match ability_name {
+            ActionAbility::Bio      => { self.bio(targets)? },
+            ActionAbility::Shield   => { self.shield(targets)? },
+            ActionAbility::Renew    => { self.renew(targets)? },
+            ActionAbility::Pierce   => { self.pierce(targets)? },
+            ActionAbility::Grenade  => { self.grenade(targets)? },
+            ActionAbility::Airraid  => { affected = self.airraid(targets)? },
+            ActionAbility::Caltrop  => { self.caltrop(targets)? },
+            ActionAbility::Spear    => { self.spear(targets)? },
+            ActionAbility::Cage     => { self.cage(targets)? },
+            ActionAbility::Drain    => { self.drain(targets)? },
+            ActionAbility::Decoy    => { self.decoy(targets, self.turn_start)? },
+            ActionAbility::Rend     => { self.rend(targets)? },
+        };
+
+        self.events.push(MatchEvent::Ability { ability: ability_name, caster, affected });
+        Ok(())
+    }
+    //Support Class
+    /// Heals allies, damages non-allies
+    fn bio(&mut self, targets: Vec<Vector>) -> Result<()> {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let heal_pow = pow * 10.0 + self.rng.gen_range(0.0, pow);
+        let curr_team = *self.entities[self.current].get_player()?;
+
+        for target in targets {
+            for player in &mut self.entities {
+                if target == player.get_pos()? { //If a player is on a targeted space
+                    if curr_team == *player.get_player()? { //If player is allied
+                        player.add_checked_hp(heal_pow)?;
+                        player.apply_effect(StatusEffect::Regenerating, 2)?;
+                    } else {  //Player is NOT allied, here we factor in armor
+                        let damage = player.get_curr_stats()?.armor_reduce(heal_pow);
+                        player.add_checked_hp(-damage)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a shield on targets with a strength equal to the user's
+    fn shield(&mut self, targets: Vec<Vector>)  -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+
+        for target in targets {
+            let cell = self.board.cell_mut(Coord::from(target))?;
+            cell.cond_with_counter(TerrainStatus::Shielded, pow as u32);
+        }
+
+        Ok(())
+    }
+
+    /// Revives downed targets with a small amount of their max hp and cleanses harmful status effects
+    /// The dead cannot be revived
+    fn renew(&mut self, targets: Vec<Vector>)   -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+
+        for target in targets {
+            for player in &mut self.entities {
+                if target == player.get_pos()? && !player.is_dead() { //If a living player is on a targeted space
+                    if player.get_status()? == Status::Downed {
+
+                        let threshold = (pow * 2.0).clamp(10.0, 100.0); //If hp remaining is below this we restore up to here, this value is between 10-100%
+
+                        let max_hp = *player.get_stats()?.get_hp();
+                        let curr_hp = *player.get_curr_stats()?.get_hp();
+                        let remaining: f32 = (curr_hp / max_hp) * 100.0; //here we have a % of max hp
+
+                        if remaining < threshold { //The min hp value is not met
+                            player.add_checked_hp(max_hp * threshold / 100.0 - curr_hp)?;
+                        }
+                    }
+
+                    player.reset_status();
+                    player.cleanse()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    //Assault Class
+    /// Shoots a piercing shot that damages and marks everything it hits that doesn't have a shield
+    /// Including shields and frozen tiles
+    fn pierce(&mut self, targets: Vec<Vector>)  -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let dmg_pow = pow * 10.0 + self.rng.gen_range(0.0, pow);
+
+        //Damage everything hit
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    player.add_checked_hp(-damage)?;
+                    player.apply_effect(StatusEffect::Marked, 2)?;
+                }
+            }
+            //Check for TerrainStatus and decrement if hit
+            match cond {
+                TerrainStatus::Shielded => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                TerrainStatus::Frozen   => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                _                       => {/*Ignore other types*/}
+            }
+
+        }
+        Ok(())
+    }
+
+    /// Deals damage, melts frozen tiles, ignites unshielded tiles, intensifies already burning tiles
+    fn grenade(&mut self, targets: Vec<Vector>)     -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let dmg_pow = pow * 10.0 + self.rng.gen_range(0.0, pow);
+
+        //Damage everything hit
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    player.add_checked_hp(-damage)?;
+                }
+            }
+            //Check for TerrainStatus and decrement if hit
+            match cond {
+                TerrainStatus::Shielded => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                TerrainStatus::Frozen   => { self.board.cell_mut(Coord::from(target))?.cond_with_counter(TerrainStatus::Normal, 0); },
+                TerrainStatus::Burning  => { self.board.cell_mut(Coord::from(target))?.inc_counter(); },
+                TerrainStatus::Normal   => { self.board.cell_mut(Coord::from(target))?.cond_with_counter(TerrainStatus::Burning, pow as u32); },
+                _                       => {/*Ignore other types*/}
+            }
+
+        }
+        Ok(())
+    }
+
+    /// Deals serious damage to random tiles within selected area, returns the tiles that were hit
+    fn airraid(&mut self, targets: Vec<Vector>)     -> Result<Vec<Vector>>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let dmg_pow = pow * 30.0 + self.rng.gen_range(0.0, pow * 2.0);
+
+        let chance_hit = self.rng.gen_range(0.333, 0.666); //gen_bool takes a probability between 0 and 1
+        let mut hits = vec![];
+
+        //Damage everything hit, destroy all shields, and destroy land
+        for target in targets {
+            if !self.rng.gen_bool(chance_hit) { continue; } // If the attack doesn't hit we skip the target
+            hits.push(target);
+
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    player.add_checked_hp(-damage)?;
+                }
+            }
+            //Check for TerrainStatus, destroy shields if present, and destroy all lands present
+            match cond {
+                TerrainStatus::Shielded => {
+                    self.board.cell_mut(Coord::from(target))?.cond_with_counter(TerrainStatus::Normal, 0);
+                    self.board.cell_mut(Coord::from(target))?.set_land(Terrain::Destroyed);
+                },
+                _ => { self.board.cell_mut(Coord::from(target))?.set_land(Terrain::Destroyed); }
+            }
+        }
+
+        Ok(hits)
+    }
+
+    //Trapper Class
+    /// Sets all unshielded land that are plains, destroyed, or roads to spiked land
+    fn caltrop(&mut self, targets: Vec<Vector>)  -> Result<()>  {
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+            let land = *cell.get_land()?;
+
+            if cond != TerrainStatus::Shielded &&
+                (land == Terrain::Plain || land == Terrain::Destroyed || land == Terrain::Road)
+            {
+                self.board.cell_mut(Coord::from(target))?.set_land(Terrain::Spikes);
+            }
+
+        }
+        Ok(())
+    }
+
+    /// Stuns the unshielded enemies the spear reaches
+    fn spear(&mut self, targets: Vec<Vector>)   -> Result<()>  {
+        self.afflict_enemies(&targets, StatusEffect::Stunned, 1)
+    }
+    /// Roots the unshielded enemies caught in the forcefield's ring
+    fn cage(&mut self, targets: Vec<Vector>)    -> Result<()>  {
+        self.afflict_enemies(&targets, StatusEffect::Rooted, 2)
+    }
+    /// Applies a status effect to every enemy of the current player on an unshielded target
+    fn afflict_enemies(&mut self, targets: &[Vector], effect: StatusEffect, turns: u32) -> Result<()> {
+        let curr_team = *self.entities[self.current].get_player()?;
+
+        for target in targets {
+            let cond = *self.board.cell(Coord::from(*target))?.get_cond()?;
+            for player in &mut self.entities {
+                if player.get_pos()? == *target && *player.get_player()? != curr_team && cond != TerrainStatus::Shielded {
+                    player.apply_effect(effect, turns)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    //Wraith Class
+    /// Damages all surrounding targets and heals user
+    /// Drains health from other players and shields (scaled to user level), damages ice
+    fn drain(&mut self, targets: Vec<Vector>) -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let lvl = self.entities[self.current].get_level()? as f32;
+        let dmg_pow = pow * 10.0 + self.rng.gen_range(0.0, pow);
+        let shield_drain = lvl * 10.0 + self.rng.gen_range(0.0, lvl);
+        let mut hp_drain: f32 = 0.0;
+
+        //Damage everything hit
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    hp_drain += damage;
+                    player.add_checked_hp(-damage)?;
+                }
+            }
+            //Check for TerrainStatus and decrement if hit
+            match cond {
+                TerrainStatus::Shielded => {
+                    hp_drain += shield_drain;
+                    self.board.cell_mut(Coord::from(target))?.decr_counter();
+                },
+                TerrainStatus::Frozen   => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                _                       => {/*Ignore other types*/}
+            }
+        }
+
+        //Add total drained hp to user
+        self.entities[self.current].add_checked_hp(hp_drain)?;
+
+        Ok(())
+    }
+    /// Freezes surrounding area, deals damage and teleports back to location this turn began at, turning invisible
+    /// Damages Shielded, stops Burning, and freezes normal
+    fn decoy(&mut self, targets: Vec<Vector>, origin: Vector) -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let dmg_pow = pow * 5.0 + self.rng.gen_range(0.0, pow);
+
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    player.add_checked_hp(-damage)?;
+                }
+            }
+
+            match cond {
+                //Damage shields
+                TerrainStatus::Shielded => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                //Slightly strengthen frozen tiles
+                TerrainStatus::Frozen   => { self.board.cell_mut(Coord::from(target))?.inc_counter(); },
+                //Extinguish burning tiles without applying effect of burning
+                TerrainStatus::Burning  => { self.board.cell_mut(Coord::from(target))?.reset_cond(); }
+                //Freeze normal tiles
+                TerrainStatus::Normal   => { self.board.cell_mut(Coord::from(target))?.cond_with_counter(TerrainStatus::Frozen, 3); },
+                _                       => {/*Ignore other types*/}
+            }
+        }
+        // Teleport back to starting loc
+        self.entities[self.current].apply_effect(StatusEffect::Invisible, 2)?;
+        let curr_loc = self.entities[self.current].get_pos()?;
+        self.entities[self.current].set_pos(origin)?;
+        self.events.push(MatchEvent::Teleported { entity: self.current, from: curr_loc, to: origin });
+
+        Ok(())
+    }
+    /// Deals damage and inflicts the Crippled and Bleeding status effects
+    /// Damages Shielded, and Frozen cells
+    fn rend(&mut self, targets: Vec<Vector>) -> Result<()>  {
+        let pow = *self.entities[self.current].get_curr_stats()?.get_power();
+        let dmg_pow = pow * 10.0 + self.rng.gen_range(0.0, pow);
+
+        //Damage everything hit
+        for target in targets {
+            let cell = self.board.cell(Coord::from(target))?;
+            let cond = *cell.get_cond()?;
+
+            //Check if it is a player and is not shielded
+            for player in &mut self.entities {
+                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
+                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
+                    player.add_checked_hp(-damage)?;
+                    player.apply_effect(StatusEffect::Crippled, 3)?;
+                    player.apply_effect(StatusEffect::Bleeding, 2)?;
+                }
+            }
+            //Check for TerrainStatus and decrement if hit
+            match cond {
+                TerrainStatus::Shielded => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                TerrainStatus::Frozen   => { self.board.cell_mut(Coord::from(target))?.decr_counter(); },
+                _                       => {/*Ignore other types*/}
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,8 +3,19 @@ This defines the various enum elements that are a part of gameplay data
 and provides functions to translate data to Atlas_Tile_Index keys
 */
 
+use serde::{Serialize, Deserialize};
+
+///The directions a player can move in or aim an ability
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Right,
+    Left,
+    Down,
+}
+
 ///The types of lands
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 #[allow(unused)]
 pub enum Terrain {
     Road,
@@ -37,6 +48,63 @@ impl Terrain {
     }
 }
 
+/// Moves are counted in half moves so Road can cost half of one
+pub const FULL_MOVE: u32 = 2;
+
+/// How a kind of land affects what stands on it, moves into it and is aimed through it
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TerrainRules {
+    pub move_cost: Option<u32>, //Half moves to enter, None if it cannot be entered
+    pub extra_cost: u32,        //Half moves also taken on entering, only as many as are left after the cost
+    pub armor_bonus: f32,       //Added to the armor of whoever stands here
+    pub blocks_lines: bool,     //Line abilities stop here
+    pub conceals: bool,         //Whoever stands here cannot be picked out by line abilities
+    pub blocks_airraid: bool,   //Air Raid cannot hit here
+    pub ends_movement: bool,    //Entering uses up the rest of the turn's moves
+    pub damage: f32,            //Damage per level taken on entering, before armor
+    pub flammability: f64,      //Chance each round of catching fire from each burning neighbour
+    pub blocks_sight: bool,     //Nothing can be seen past here in fog of war
+}
+
+impl TerrainRules {
+    /// Returns true if a player with `moves` half moves left may enter, which takes the land's whole cost
+    pub fn can_enter(&self, moves: u32) -> bool {
+        match self.move_cost {
+            Some(cost) => moves >= cost,
+            None       => false,
+        }
+    }
+
+    /// Returns the half moves left after entering with `moves` half moves
+    pub fn moves_after(&self, moves: u32) -> u32 {
+        if self.ends_movement { return 0; }
+        let left = moves - self.move_cost.unwrap_or(moves).min(moves);
+        left - self.extra_cost.min(left)
+    }
+}
+
+impl Terrain {
+    ///Gets the rules of the land, every terrain rule is defined here
+    pub fn rules(&self) -> TerrainRules {
+        //Spikes are entered like Plain and then take another move if one is left
+        let (move_cost, extra_cost, armor_bonus, blocks_lines, conceals, blocks_airraid, ends_movement, damage, flammability, blocks_sight) = match self {
+            Terrain::Road       => (Some(1),   0,   0.0,   false, false,  false,  false, 0.0,   0.0,   false),
+            Terrain::Plain      => (Some(2),   0,   0.0,   false, false,  false,  false, 0.0,   0.0,   false),
+            Terrain::Forest     => (Some(2),   0,   0.0,   false, true,   false,  false, 0.0,   0.35,  true),
+            Terrain::Mountain   => (Some(4),   0,   2.0,   false, false,  false,  false, 0.0,   0.0,   true),
+            Terrain::City       => (Some(2),   0,   0.0,   false, false,  true,   false, 0.0,   0.2,   false),
+            Terrain::Wall       => (None,      0,   0.0,   true,  false,  false,  false, 0.0,   0.0,   true),
+            Terrain::Pit        => (Some(2),   0,   0.0,   false, false,  false,  true,  10.0,  0.0,   false),
+            Terrain::Spikes     => (Some(2),   2,   0.0,   false, false,  false,  false, 20.0,  0.0,   false),
+            Terrain::Destroyed  => (Some(2),   0,   0.0,   false, false,  false,  false, 0.0,   0.0,   false),
+            Terrain::Empty      => (None,      0,   0.0,   true,  false,  true,   false, 0.0,   0.0,   false),
+        };
+        TerrainRules {
+            move_cost, extra_cost, armor_bonus, blocks_lines, conceals, blocks_airraid, ends_movement, damage, flammability, blocks_sight,
+        }
+    }
+}
+
 /// Translates a key string to a Terrain enum if possible, returns None if not found
 pub fn to_terrain(key: &str) -> Option<Terrain> {
     match key {
@@ -55,7 +123,7 @@ pub fn to_terrain(key: &str) -> Option<Terrain> {
 }
 
 ///The conditions a land can be in.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum TerrainStatus {
     Normal,
     Burning,
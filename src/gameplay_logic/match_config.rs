@@ -0,0 +1,100 @@
+/*
+The choices made in the setup scene before a match starts. A match is built entirely from these,
+so restarting it from the pause menu gives the same teams, board and rules.
+*/
+
+use crate::gameplay_logic::entities::{Entity, ClassType, PlayerType};
+use crate::gameplay_logic::game_board::GameBoard;
+use crate::gameplay_logic::game_levels::Levels;
+use crate::gameplay_logic::turn_order::TurnScheme;
+use crate::game_logic::preferences::Opponent;
+
+use quicksilver::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::io;
+
+/// Stages the monster can start the match in, from weakest to strongest
+pub const MONSTER_STAGES: [ClassType; 3] = [ClassType::Wraith, ClassType::Kraken, ClassType::Elder];
+/// Highest level hunters can start at, every hunter ability is unlocked by level 3
+pub const MAX_HUNTER_LEVEL: u32 = 3;
+
+/// Describes how a match is set up
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct MatchConfig {
+    pub level: usize, //Level number from `Levels`
+    pub player_one: PlayerType, //Team played by the first human, the second human plays the other
+    pub hunter_level: u32,
+    pub monster_stage: ClassType,
+    pub turn_scheme: TurnScheme,
+    //Optional rules
+    pub level_conditions: bool, //Start with the level's burning, frozen and shielded cells
+    pub round_limit: Option<u32>, //The Elder God wins by surviving this many rounds
+    pub fog_of_war: bool, //Each team only sees what its members can see
+    pub handoff: bool, //Hotseat players pass the screen between teams and the monster moves unseen
+    pub seed: u64, //Seeds damage rolls and the environment, every new or restarted match rolls a new one
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            player_one: PlayerType::Player2,
+            hunter_level: 1,
+            monster_stage: ClassType::Wraith,
+            turn_scheme: TurnScheme::Interleaved,
+            level_conditions: true,
+            round_limit: None,
+            fog_of_war: false,
+            handoff: false,
+            seed: 0,
+        }
+    }
+}
+
+impl MatchConfig {
+    /// Sets the first human's team so they face their preferred opponent
+    pub fn set_opponent(&mut self, opponent: Opponent) {
+        self.player_one = match opponent {
+            Opponent::ElderGod => PlayerType::Player2,
+            Opponent::Hunters  => PlayerType::Player1,
+        };
+    }
+
+    /// Returns an error naming the first choice a match cannot be set up with
+    /// The setup scene only offers valid choices, configs from elsewhere are checked with this before use
+    pub fn check(&self) -> Result<()> {
+        let reason = if Levels::new()?.get_level(self.level).is_none() {
+            format!("There is no level {}", self.level)
+        } else if self.hunter_level < 1 || self.hunter_level > MAX_HUNTER_LEVEL {
+            format!("Hunters start between level 1 and {}, not {}", MAX_HUNTER_LEVEL, self.hunter_level)
+        } else if !MONSTER_STAGES.contains(&self.monster_stage) {
+            format!("The monster cannot start as {:?}", self.monster_stage)
+        } else {
+            return Ok(());
+        };
+        Err(io::Error::new(io::ErrorKind::InvalidInput, reason).into())
+    }
+
+    /// Creates the entities at the level's starting positions, the monster is always first followed by
+    /// the Support, Assault and Trapper
+    pub fn create_entities(&self) -> Result<Vec<Entity>> {
+        let (monster_pos, hunter_pos) = Levels::new()?.get_spawns(self.level)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Level {} has no spawns", self.level)))?;
+
+        let monster = Entity::new_char(self.monster_stage, PlayerType::Player1, 1, monster_pos, false)?;
+        let support = Entity::new_char(ClassType::Support, PlayerType::Player2, self.hunter_level, hunter_pos[0], false)?;
+        let assault = Entity::new_char(ClassType::Assault, PlayerType::Player2, self.hunter_level, hunter_pos[1], false)?;
+        let trapper = Entity::new_char(ClassType::Trapper, PlayerType::Player2, self.hunter_level, hunter_pos[2], false)?;
+
+        Ok(vec![monster, support, assault, trapper])
+    }
+
+    /// Creates the board for the level, clearing its starting conditions if that rule is off
+    pub fn create_board(&self) -> Result<GameBoard> {
+        let mut board = GameBoard::new(self.level)?;
+        if !self.level_conditions {
+            board.clear_conditions()?;
+        }
+        Ok(board)
+    }
+}
@@ -8,7 +8,22 @@ level.
 
 ///Logic that manages the games board
 pub mod game_board;
+///Whole number positions on the board
+pub mod coord;
 pub mod gameplay_type;
 pub mod game_levels;
 pub mod entities;
-pub mod animator;
\ No newline at end of file
+pub mod animator;
+pub mod effects;
+///Choices a match is set up from
+pub mod match_config;
+///Decides who takes the next turn
+pub mod turn_order;
+///What each team can see in fog of war
+pub mod field_of_view;
+///The rules of a match, without graphics or input
+pub mod game_match;
+///Draws a match as text for terminals
+pub mod ascii_board;
+///Text records of a match that tests compare
+pub mod snapshot;
\ No newline at end of file
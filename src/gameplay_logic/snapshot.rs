@@ -0,0 +1,169 @@
+/*
+Stable text records of a board and its entities, so tests can compare two states and see exactly what changed.
+A snapshot is a list of records, one line each: what the record describes, then its `field=value` pairs. The
+fields come from the same serialization a match is saved and sent with, so new fields show up without this
+file changing, and they are sorted by name so the text only changes when the state does.
+*/
+
+use crate::gameplay_logic::entities::{Entity, STATUS_EFFECTS};
+use crate::gameplay_logic::game_board::GameBoard;
+use crate::gameplay_logic::game_match::Match;
+
+use quicksilver::prelude::*;
+use serde_json::Value;
+use std::fmt;
+use std::io;
+
+/// Flattens a serialized value into `field=value` pairs, nested fields are joined with dots
+fn flatten(prefix: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    let name = |key: &str| if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) };
+    match value {
+        Value::Object(map)   => for (key, value) in map { flatten(&name(key), value, fields) },
+        Value::Array(values) => for (index, value) in values.iter().enumerate() { flatten(&name(&index.to_string()), value, fields) },
+        Value::String(text)  => fields.push((prefix.to_string(), text.clone())),
+        Value::Null          => fields.push((prefix.to_string(), "none".to_string())),
+        value                => fields.push((prefix.to_string(), value.to_string())),
+    }
+}
+
+/// Serializes a value into `field=value` pairs
+fn fields_of<T: serde::Serialize>(value: &T) -> Result<Vec<(String, String)>> {
+    let value = serde_json::to_value(value).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let mut fields = vec![];
+    flatten("", &value, &mut fields);
+    Ok(fields)
+}
+
+/// One line of a snapshot
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    key: String, //What the record describes, such as `cell 3,4` or `entity 0`
+    fields: Vec<(String, String)>,
+}
+
+impl Record {
+    /// Returns the value of a field
+    fn get(&self, field: &str) -> Option<&str> {
+        self.fields.iter().find(|(name, _)| name == field).map(|(_, value)| value.as_str())
+    }
+}
+
+/// A field that differs between two snapshots, a missing record or field has no value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub record: String,
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        write!(f, "{} {}: {} -> {}", self.record, self.field, value(&self.before), value(&self.after))
+    }
+}
+
+/// A stable text record of a board, its entities or a whole match
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Snapshot {
+    records: Vec<Record>,
+}
+
+impl Snapshot {
+    /// Records every cell of a board, keyed by position
+    pub fn of_board(board: &GameBoard) -> Result<Self> {
+        let mut records = vec![];
+        for cell in board.cells() {
+            let pos = cell.get_pos()?;
+            let fields = fields_of(cell)?.into_iter().filter(|(name, _)| !name.starts_with("pos.")).collect();
+            records.push(Record { key: format!("cell {},{}", pos.x, pos.y), fields });
+        }
+        Ok(Self { records })
+    }
+
+    /// Records every entity, keyed by index, with each status effect named
+    pub fn of_entities(entities: &[Entity]) -> Result<Self> {
+        let mut records = vec![];
+        for (index, entity) in entities.iter().enumerate() {
+            let fields = fields_of(entity)?.into_iter().map(|(name, value)| {
+                let effect = name.strip_prefix("effects.").and_then(|slot| slot.parse::<usize>().ok());
+                match effect {
+                    Some(slot) => (format!("effects.{:?}", STATUS_EFFECTS[slot]), value),
+                    None       => (name, value),
+                }
+            }).collect();
+            records.push(Record { key: format!("entity {}", index), fields });
+        }
+        Ok(Self { records })
+    }
+
+    /// Records whose turn it is, the board and the entities of a match
+    pub fn of_match(game: &Match) -> Result<Self> {
+        let fields = vec![
+            ("round".to_string(), game.round().to_string()),
+            ("current".to_string(), game.current().to_string()),
+            ("moves".to_string(), game.moves().to_string()),
+            ("actions".to_string(), game.actions().to_string()),
+            ("winner".to_string(), format!("{:?}", game.winner())),
+        ];
+        let mut records = vec![Record { key: "match".to_string(), fields }];
+        records.extend(Snapshot::of_board(game.board())?.records);
+        records.extend(Snapshot::of_entities(game.entities())?.records);
+        Ok(Self { records })
+    }
+
+    /// Reads a snapshot written by `to_string`, such as a golden file
+    pub fn parse(text: &str) -> Self {
+        let records = text.lines().filter_map(|line| {
+            let (key, fields) = line.split_once(':')?;
+            let fields = fields.split_whitespace().filter_map(|field| field.split_once('='))
+                .map(|(name, value)| (name.to_string(), value.to_string())).collect();
+            Some(Record { key: key.to_string(), fields })
+        }).collect();
+        Self { records }
+    }
+
+    /// Returns every field that differs from `after`, in the order the records and fields appear
+    pub fn diff(&self, after: &Snapshot) -> Vec<Change> {
+        let mut changes = vec![];
+        let mut compare = |key: &str, before: Option<&Record>, after: Option<&Record>| {
+            let mut names: Vec<&str> = before.iter().chain(after.iter()).flat_map(|record| record.fields.iter())
+                .map(|(name, _)| name.as_str()).collect();
+            //Keep the first appearance of each field
+            let mut seen = vec![];
+            names.retain(|name| if seen.contains(name) { false } else { seen.push(*name); true });
+            for name in names {
+                let (old, new) = (before.and_then(|record| record.get(name)), after.and_then(|record| record.get(name)));
+                if old != new {
+                    changes.push(Change { record: key.to_string(), field: name.to_string(),
+                                          before: old.map(str::to_string), after: new.map(str::to_string) });
+                }
+            }
+        };
+
+        for record in self.records.iter() {
+            compare(&record.key, Some(record), after.records.iter().find(|other| other.key == record.key));
+        }
+        for record in after.records.iter().filter(|record| !self.records.iter().any(|other| other.key == record.key)) {
+            compare(&record.key, None, Some(record));
+        }
+        changes
+    }
+
+    /// Writes the changes from this snapshot to `after`, one per line
+    pub fn diff_text(&self, after: &Snapshot) -> String {
+        self.diff(after).iter().map(|change| format!("{}\n", change)).collect()
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for record in self.records.iter() {
+            write!(f, "{}:", record.key)?;
+            for (name, value) in record.fields.iter() { write!(f, " {}={}", name, value)?; }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+/*
+Decides which entity takes the next turn. Turns are dealt out a round at a time following the scheme
+chosen for the match, and dead entities are skipped so they never get a turn.
+*/
+
+use crate::gameplay_logic::entities::{Entity, PlayerType};
+
+use quicksilver::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+
+/// Ways turns can be ordered within a round
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum TurnScheme {
+    Interleaved, //The monster acts between every hunter
+    TeamPhase,   //Every hunter acts, then the monster
+    Initiative,  //Everyone acts once, fastest first
+    Reactive,    //Hunters act in turn, the monster acts at the end of the round and reacts to hunters that end next to it
+}
+
+/// Every scheme in the order they are listed in setup
+pub const TURN_SCHEMES: [TurnScheme; 4] = [TurnScheme::Interleaved, TurnScheme::TeamPhase, TurnScheme::Initiative, TurnScheme::Reactive];
+
+impl TurnScheme {
+    /// Returns the name shown in setup
+    pub fn name(&self) -> &'static str {
+        match self {
+            TurnScheme::Interleaved => "Interleaved",
+            TurnScheme::TeamPhase   => "Team Phase",
+            TurnScheme::Initiative  => "Initiative",
+            TurnScheme::Reactive    => "Reactive",
+        }
+    }
+
+    /// Returns the entity indices in the order they act during one round
+    fn round_order(&self, entities: &[Entity]) -> Result<Vec<usize>> {
+        let mut hunters = vec![];
+        let mut monsters = vec![];
+        for (index, entity) in entities.iter().enumerate() {
+            match entity.get_player()? {
+                PlayerType::Player1 => monsters.push(index),
+                PlayerType::Player2 => hunters.push(index),
+                _                   => {/*Undetermined entities do not take turns*/},
+            }
+        }
+
+        let order = match self {
+            TurnScheme::Interleaved => {
+                let mut order = vec![];
+                for (count, hunter) in hunters.iter().enumerate() {
+                    if count > 0 { order.extend(monsters.iter()); }
+                    order.push(*hunter);
+                }
+                if hunters.is_empty() { order.extend(monsters.iter()); }
+                order
+            },
+            TurnScheme::TeamPhase | TurnScheme::Reactive => {
+                hunters.extend(monsters.iter());
+                hunters
+            },
+            TurnScheme::Initiative  => {
+                let mut speeds = vec![];
+                for index in hunters.iter().chain(monsters.iter()) {
+                    speeds.push((*index, *entities[*index].get_stats()?.get_speed()));
+                }
+                //Sorting is stable so ties keep hunters first, in their usual order
+                speeds.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                speeds.into_iter().map(|(index, _)| index).collect()
+            },
+        };
+
+        Ok(order)
+    }
+}
+
+/// Tracks the turns left in the current round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnOrder {
+    scheme: TurnScheme,
+    queue: VecDeque<usize>,
+    round: u32,
+    last: Option<usize>, //Entity that took the previous turn
+}
+
+impl TurnOrder {
+    /// Creates a turn order, the first call to `next` starts round 1
+    pub fn new(scheme: TurnScheme) -> Self {
+        Self {
+            scheme,
+            queue: VecDeque::new(),
+            round: 0,
+            last: None,
+        }
+    }
+
+    /// Returns the current round, counting from 1 once the first turn is dealt
+    pub fn round(&self) -> u32 { self.round }
+
+    /// Returns the index of the entity that acts next, skipping dead entities and starting a new round when this one runs out
+    /// Returns None if no entity is able to act
+    pub fn next(&mut self, entities: &[Entity]) -> Result<Option<usize>> {
+        if self.scheme == TurnScheme::Reactive {
+            if let Some(monster) = self.reaction(entities)? {
+                self.last = Some(monster);
+                return Ok(Some(monster));
+            }
+        }
+
+        //The rest of this round, then a fresh one
+        for _ in 0..2 {
+            while let Some(index) = self.queue.pop_front() {
+                if !entities[index].is_dead() {
+                    self.last = Some(index);
+                    return Ok(Some(index));
+                }
+            }
+            self.round += 1;
+            self.queue = self.scheme.round_order(entities)?.into();
+        }
+
+        Ok(None)
+    }
+
+    /// Returns a living monster next to the hunter that just acted, if it is not about to act anyway
+    fn reaction(&self, entities: &[Entity]) -> Result<Option<usize>> {
+        let hunter = match self.last {
+            Some(index) if *entities[index].get_player()? == PlayerType::Player2 => &entities[index],
+            _ => return Ok(None),
+        };
+        let hunter_pos = hunter.get_pos()?;
+
+        for (index, entity) in entities.iter().enumerate() {
+            if *entity.get_player()? != PlayerType::Player1 || entity.is_dead() { continue; }
+            let pos = entity.get_pos()?;
+            let adjacent = (pos.x - hunter_pos.x).abs() + (pos.y - hunter_pos.y).abs() <= 1.0;
+            if adjacent && self.queue.front() != Some(&index) {
+                return Ok(Some(index));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,9 @@
+//Quicksilver's error type grows large with the gamepads feature, every scene returns it so it cannot be boxed here
+#![allow(clippy::result_large_err)]
+
+//The game is a library so the window in main.rs and the headless tools in src/bin share it
+
+//Declare as 'use'able modules
+pub mod game_logic;
+pub mod gameplay_logic;
+pub mod scenes;
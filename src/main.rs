@@ -1,9 +1,4 @@
-//Declare as 'use'able modules
-mod game_logic;
-mod gameplay_logic;
-mod scenes;
-
-use game_logic::main_state::Game;
+use i_am_the_elder_god::game_logic::main_state::Game;
 
 //Resources
 use quicksilver::prelude::*;
@@ -1,17 +1,27 @@
 use crate::game_logic::scene_type::SceneReturn;
 use crate::gameplay_logic::entities::*;
-use crate::gameplay_logic::animator::Animator;
-use crate::gameplay_logic::game_board::GameBoard;
-use crate::gameplay_logic::gameplay_type::{TerrainStatus, Terrain};
+use crate::gameplay_logic::animator::{Animator, Tween, Playback, Easing, Callback, Clock};
+use crate::gameplay_logic::effects::{self, Effect};
+use crate::gameplay_logic::game_match::{Match, Command, MatchEvent, Sign};
+use crate::gameplay_logic::match_config::MatchConfig;
+use crate::gameplay_logic::coord::Coord;
+use crate::gameplay_logic::field_of_view;
+use crate::gameplay_logic::gameplay_type::{Direction, TerrainStatus, FULL_MOVE};
 use crate::game_logic::draw_helper::*;
+use crate::game_logic::network::{NetRole, Connection};
+use crate::game_logic::sound_bank::{SoundBank, SoundEvent};
+use crate::game_logic::preferences::{Preferences, Palette};
+use crate::game_logic::input_map::{InputMap, InputAction, PadSlot, Controls};
 
 //Resources
 use quicksilver::prelude::*;
 use quicksilver::graphics::Atlas;
+use rand::Rng;
 //Std
+use std::cell::RefCell;
 use std::iter::Cycle;
+use std::rc::Rc;
 use std::vec::IntoIter;
-use rand::Rng;
 
 #[derive(PartialEq)]
 enum ActionType {
@@ -20,12 +30,26 @@ enum ActionType {
     End,
 }
 
+/// Choices in the pause menu, top to bottom
 #[derive(PartialEq, Clone, Copy)]
-pub enum Direction {
-    Up,
-    Right,
-    Left,
-    Down,
+enum PauseItem {
+    Resume,
+    Restart,
+    Concede,
+    Settings,
+    Quit,
+}
+
+const PAUSE_ITEMS: [PauseItem; 5] = [PauseItem::Resume, PauseItem::Restart, PauseItem::Concede, PauseItem::Settings, PauseItem::Quit];
+
+impl PauseItem {
+    /// True for choices that end or throw away the match and must be confirmed
+    fn needs_confirm(&self) -> bool {
+        match self {
+            PauseItem::Restart | PauseItem::Concede | PauseItem::Quit => true,
+            PauseItem::Resume | PauseItem::Settings                   => false,
+        }
+    }
 }
 
 pub struct ElderGame {
@@ -43,6 +67,12 @@ pub struct ElderGame {
     info_text: Asset<Image>,
     abilities_text: Asset<Image>,
     underline: Asset<Image>,
+    //Pause menu, one item and one confirmation question per choice
+    pause_title: Asset<Image>,
+    pause_items: Vec<Asset<Image>>,
+    pause_questions: Vec<Asset<Image>>,
+    pause_help: Asset<Image>,
+    confirm_help: Asset<Image>,
     //White
     move_text: Asset<Image>, action_text: Asset<Image>, end_text: Asset<Image>,
     //Grey
@@ -72,20 +102,20 @@ pub struct ElderGame {
     caltrop_help: Asset<Image>, spear_help: Asset<Image>, cage_help: Asset<Image>,
     //--------Text End-------//
 
-    //game_board layer
-    game_board: GameBoard,
+    //The board, players and turns, only changed through commands
+    game: Match,
 
-    //Player related data
-    turn_start_loc: Vector, //Location the current player starts their turn at
-    player_ref: Vec<Entity>, // players
-    turn_order: Cycle<IntoIter<usize>>, // turn index iterator
-    curr_player: usize, //index of current player
+    //Match setup, the match is rebuilt from this on reset
+    config: MatchConfig,
+
+    //Network play, the role is chosen on the command line and None plays on one screen
+    role: Option<NetRole>,
+    connection: Option<Connection>,
+    follow: PlayerType, //Team a spectator sees the board through
 
     //Turn control data - [Move, Action, End]
     end_flag: bool,
     action_state: ActionType,
-    moves: u32,
-    actions: u32,
     curr_dir: Direction,
     directions: Cycle<IntoIter<Direction>>,
 
@@ -95,11 +125,35 @@ pub struct ElderGame {
     //Atlas supports keys A-Z, Blank (# is the same tile), and Null (with the '-' key)
     game_tiles: Asset<Atlas>,
     token_tiles: Asset<Atlas>,
+    clock: Clock,
     selectable_animator: Animator,
-    soft_click: Asset<Sound>,
-    click: Asset<Sound>,
-
-    winner: PlayerType,
+    token_motion: Vec<(usize, u64, Tween)>, //Tokens sliding between cells, keyed by player index
+    effects: Vec<(Vector, u64, Animator)>, //One-shot effects drawn over board cells
+    ability_effects: Vec<(u64, Effect)>, //Ability specific effects, input waits for these to finish
+    next_animation: u64, //Id given to the next one-shot, its completion callback reports it
+    finished_animations: Rc<RefCell<Vec<u64>>>, //Ids reported by completion callbacks since the last update
+    sound_queue: Vec<SoundEvent>, //Sounds caused during an update, played when it ends
+
+    //Preferences from the settings scene
+    animation_speed: f64,
+    palette: Palette,
+    input: InputMap,
+    elder_god_pad: PadSlot, //Gamepads that drive each team
+    hunters_pad: PadSlot,
+
+    //Pause menu state, the match is frozen while paused
+    paused: bool,
+    pause_selection: usize,
+    confirming: bool, //Waiting for the selected choice to be confirmed
+
+    //Hotseat handoff, the board stays covered until the team taking over is ready
+    handoff: Option<PlayerType>,
+    handoff_titles: [Asset<Image>; 2], //Elder God, Hunters
+    handoff_help: Asset<Image>,
+    waiting_text: Asset<Image>, //Shown while the other screen of a networked match is away
+    mail_text: Asset<Image>, //Shown while the other team's turn file is awaited
+    mail_refusal: Option<(String, Asset<Image>)>, //Why the awaited turn file was refused, shown in place of the mail text
+    watch_help: [Asset<Image>; 2], //Following the Elder God, Hunters
 }
 
 //This is here to prevent silly warning about flags
@@ -114,16 +168,10 @@ impl ElderGame {
         let atlas_index = "Atlas_Tile_Index";
         let game_atlas_index = "Atlas_Game_Index";
         let underline = "line.png";
-        let click_soft = "SoftClick.wav";
-        let click_hard = "Click.wav";
 
         //Help text
-        let move_help = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("Arrow Keys to move, 0-end game", &FontStyle::new(20.0, Color::BLACK), )}));
-        let action_help = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("Up/Down-Scroll Left/Right-Aim + Enter", &FontStyle::new(20.0, Color::BLACK), )}));
-        let end_help = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("Ending turn... 0-end game", &FontStyle::new(20.0, Color::BLACK), )}));
+        let input = InputMap::default();
+        let [move_help, action_help, end_help] = ElderGame::render_key_help(&input);
 
         //Ability Labels
         //Wraith
@@ -218,20 +266,31 @@ impl ElderGame {
         let abilities_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
             font.render("[Action]", &FontStyle::new(17.0, Color::WHITE), )}));
 
-        //Menu Words
-        let move_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[M]ove", &FontStyle::new(16.0, Color::WHITE), )}));
-        let action_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[A]ction", &FontStyle::new(16.0, Color::WHITE), )}));
-        let end_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[E]nd", &FontStyle::new(16.0, Color::WHITE),)}));
-        //Grey Words
-        let move_grey = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[M]ove", &FontStyle::new(16.0, Color::from_rgba(132, 126, 135, 255.0)), )}));
-        let action_grey = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[A]ction", &FontStyle::new(16.0, Color::from_rgba(132, 126, 135, 255.0)), )}));
-        let end_grey = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render("[E]nd", &FontStyle::new(16.0, Color::from_rgba(132, 126, 135, 255.0)), )}));
+        //Pause menu
+        let pause_title = Asset::new(Font::load(font_mononoki).and_then(|font| {
+            font.render("Paused", &FontStyle::new(30.0, Color::WHITE), )}));
+        let pause_items = ["Resume", "Restart", "Concede", "Settings", "Quit to Title"].iter()
+            .map(|item| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+                font.render(item, &FontStyle::new(20.0, Color::WHITE), )})))
+            .collect();
+        let pause_questions = ["", "Restart the match?", "Concede the match to the other team?", "", "Quit to the title?"].iter()
+            .map(|question| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+                font.render(question, &FontStyle::new(20.0, Color::WHITE), )})))
+            .collect();
+        let [pause_help, confirm_help, handoff_help] = ElderGame::render_pause_help(&input);
+        let watch_help = ElderGame::render_watch_help(&input);
+        let handoff_titles = ["Pass to the Elder God", "Pass to the Hunters"]
+            .map(|title| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+                font.render(title, &FontStyle::new(30.0, Color::WHITE), )})));
+
+        let waiting_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
+            font.render("Waiting for the other player to connect...", &FontStyle::new(20.0, Color::BLACK), )}));
+        let mail_text = Asset::new(Font::load(font_mononoki).and_then(|font| {
+            font.render("Waiting for the other team's turn file...", &FontStyle::new(20.0, Color::BLACK), )}));
+
+        //Menu Words, white and grey
+        let [move_text, action_text, end_text] = ElderGame::render_key_menu(&input, Color::WHITE);
+        let [move_grey, action_grey, end_grey] = ElderGame::render_key_menu(&input, Color::from_rgba(132, 126, 135, 255.0));
         //Stat Labels
         let sat_label = Asset::new(Font::load(font_mononoki).and_then(|font| {
             font.render("SAT", &FontStyle::new(14.0, Color::WHITE), )}));
@@ -245,48 +304,18 @@ impl ElderGame {
         let team_label = Asset::new(Font::load(font_mononoki).and_then(|font| {
             font.render("Team:", &FontStyle::new(14.0, Color::WHITE),)}));
 
-        //Create players
-        let wraith = Entity::new_char(ClassType::Wraith, PlayerType::Player1, 1,
-                                      Vector::new(9,11), false)
-                                        .expect("Cannot create Wraith game::new.");
-        let support = Entity::new_char(ClassType::Support, PlayerType::Player2, 1,
-                                       Vector::new(6,4), false)
-            .expect("Cannot create Support game::new.");
-        let assault = Entity::new_char(ClassType::Assault, PlayerType::Player2, 1,
-                                       Vector::new(9,3), false)
-            .expect("Cannot create Assault game::new.");
-        let trapper = Entity::new_char(ClassType::Trapper, PlayerType::Player2, 1,
-                                       Vector::new(12,4), false)
-            .expect("Cannot create Trapper game::new.");
-
-        //Player Vector
-        let player_ref = vec![wraith, support, assault, trapper];
-        //Player Turn order, there must be no elements greater than `player_ref.len()-1`
-        let mut turn_order = vec![1,0,2,0,3].into_iter().cycle();
-        let curr_player = turn_order.next().expect("Cannot find first player");
-        //Find first player's stats
-        let moves = *player_ref[curr_player].get_stats()?.get_speed() as u32;
-        let actions = *player_ref[curr_player].get_stats()?.get_actions() as u32;
-
-        //Setup ability selection
-        let mut selections = vec![0,1,2].into_iter().cycle();
-        let curr_selection = selections.next().expect("Cannot find first selection");
-
-        //Setup Sound Asssets
-        let soft_click = Asset::new(Sound::load(click_soft));
-        let click = Asset::new(Sound::load(click_hard));
-
-        //Setup psuedo animation
-        let animation_keys = vec!["S1".to_string(),"S2".to_string(),"S3".to_string(),"S4".to_string(),"S5".to_string()].into_iter().cycle();
+        //Setup selection highlight animation
+        let animation_keys = vec!["S1".to_string(),"S2".to_string(),"S3".to_string(),"S4".to_string(),"S5".to_string()];
 
-        //Setup direction tracker
-        let mut directions = vec![Direction::Up, Direction::Right, Direction::Down, Direction::Left].into_iter().cycle();
+        //The match itself is created by `reset`
+        let config = MatchConfig::default();
 
-        Ok(Self {
+        let mut elder_game = Self {
             game_background: Asset::new(Image::load(background)),
             game_overlay: Asset::new(Image::load(overlay)),
             move_help, action_help, end_help,
             controls_text, class_text, info_text, abilities_text,
+            pause_title, pause_items, pause_questions, pause_help, confirm_help,
             underline: Asset::new(Image::load(underline)),
             //[Press] Labels
             move_text, action_text, end_text,
@@ -312,149 +341,323 @@ impl ElderGame {
             caltrop_white, spear_white, cage_white,
             caltrop_help, spear_help, cage_help,
 
-            game_board: GameBoard::new().expect("Failed to load GameBoard in scenes::game::ElderGame::new"),
-            turn_start_loc: player_ref[curr_player].get_pos()?,
-            player_ref,
-            turn_order,
-            curr_player,
-
+            game: Match::new(config)?,
+            config,
+            role: NetRole::from_args(),
+            connection: None,
+            follow: PlayerType::Player2,
 
             //Turn control data
             end_flag: false,
             action_state: ActionType::Move,
-            curr_dir: directions.next().expect("Cannot load initial direction in scenes::game::ElderGame::new"),
-            directions: directions,
-            moves, actions,
-            selections, curr_selection,
+            curr_dir: Direction::Up,
+            directions: vec![].into_iter().cycle(),
+            selections: vec![].into_iter().cycle(), curr_selection: 0,
 
             game_tiles: Asset::new(Atlas::load(atlas_index)),
             token_tiles: Asset::new(Atlas::load(game_atlas_index)),
-            selectable_animator: Animator::new(animation_keys, 0.17)?,
-
-            soft_click, click,
+            clock: Clock::new(),
+            selectable_animator: Animator::uniform(animation_keys, 0.17, Playback::Loop)?,
+            token_motion: vec![],
+            effects: vec![],
+            ability_effects: vec![],
+            next_animation: 0,
+            finished_animations: Rc::new(RefCell::new(vec![])),
+
+            sound_queue: vec![],
+
+            animation_speed: 1.0,
+            palette: Palette::Standard,
+            input,
+            elder_god_pad: PadSlot::Any,
+            hunters_pad: PadSlot::Any,
+
+            paused: false,
+            pause_selection: 0,
+            confirming: false,
+
+            handoff: None,
+            handoff_titles, handoff_help, waiting_text, mail_text, mail_refusal: None, watch_help,
+        };
+        elder_game.reset()?;
 
-            winner: PlayerType::Undetermined,
-        })
+        Ok(elder_game)
     }
 
     /// Process keyboard and mouse, update the game state
-    pub fn update(&mut self, window: &mut Window) -> Result<SceneReturn> {
-        use ButtonState::*;
-        let mut retval = SceneReturn::Good;
-        let kb = window.keyboard();
+    /// Sounds caused by the update are played through `sounds` once it is done
+    pub fn update(&mut self, window: &mut Window, sounds: &mut SoundBank) -> Result<SceneReturn> {
+        self.poll_network()?;
+        let mut retval = if self.handoff.is_some() { self.update_handoff(window)? }
+                         else if self.paused { self.update_pause(window)? }
+                         else { self.update_turn(window)? };
+        //A networked match can end on the other screen whatever this one is doing
+        if retval == SceneReturn::Good { retval = self.check_game()?; }
+        if retval == SceneReturn::Finished && self.game.winner() != PlayerType::Undetermined {
+            self.sound_queue.push(SoundEvent::Victory);
+        }
+
+        for event in self.sound_queue.drain(..) {
+            sounds.play(event)?;
+        }
+
+        Ok(retval)
+    }
+
+    /// Waits for the team taking over to confirm they have the screen
+    fn update_handoff(&mut self, window: &mut Window) -> Result<SceneReturn> {
+        let controls = self.controls(window)?;
+        if self.input.pressed(&controls, InputAction::Confirm) {
+            self.sound_queue.push(SoundEvent::UiConfirm);
+            self.handoff = None;
+        }
+        Ok(SceneReturn::Good)
+    }
+
+    /// Processes input for the current turn
+    /// In a networked match only the local team's turns are played here, the rest are watched
+    /// A spectator plays no turns and instead switches which team it follows
+    fn update_turn(&mut self, window: &mut Window) -> Result<SceneReturn> {
+        let delta = self.clock.tick();
+        self.animate(delta * self.animation_speed);
+        //Input is ignored until tokens and effects come to rest
+        if self.is_animating() { return Ok(SceneReturn::Good); }
+        let controls = self.controls(window)?;
+
+        if self.watching() {
+            if self.input.pressed(&controls, InputAction::Confirm) {
+                self.sound_queue.push(SoundEvent::UiConfirm);
+                self.follow = if self.follow == PlayerType::Player1 { PlayerType::Player2 } else { PlayerType::Player1 };
+            }
+        } else if self.plays_turn()? {
+            self.update_commands(&controls)?;
+        }
+
+        //Conceding goes straight to its confirmation
+        if self.input.pressed(&controls, InputAction::Concede) {
+            self.sound_queue.push(SoundEvent::UiConfirm);
+            self.pause(PauseItem::Concede);
+            self.confirming = true;
+        } else if self.input.pressed(&controls, InputAction::Cancel) {
+            self.sound_queue.push(SoundEvent::UiConfirm);
+            self.pause(PauseItem::Resume);
+        }
+
+        Ok(SceneReturn::Good)
+    }
 
-        let curr_loc = self.player_ref[self.curr_player].get_pos()?;
-        let mut moved = false;
-        let mut acted = false;
+    /// Turns the current player's input into commands for the match
+    fn update_commands(&mut self, controls: &Controls) -> Result<()> {
+        let up = self.input.pressed(controls, InputAction::Up);
+        let left = self.input.pressed(controls, InputAction::Left);
+        let down = self.input.pressed(controls, InputAction::Down);
+        let right = self.input.pressed(controls, InputAction::Right);
+        let (moves, actions) = (self.game.moves(), self.game.actions());
 
         //Change ActionState - disallow swap if nonsensical
-        if kb[Key::M] == Pressed && self.moves > 0   {
-            self.click.execute(|music| { music.play() })?;
+        let move_pressed = self.input.pressed(controls, InputAction::MoveMode);
+        let action_pressed = self.input.pressed(controls, InputAction::ActionMode);
+        if move_pressed && moves > 0   {
+            self.sound_queue.push(SoundEvent::UiConfirm);
             self.action_state = ActionType::Move;
         }
-        else if kb[Key::M] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
+        else if move_pressed { self.sound_queue.push(SoundEvent::UiDeny); }
 
-        if kb[Key::A] == Pressed && self.actions > 0 {
-            self.click.execute(|music| { music.play() })?;
+        if action_pressed && actions > 0 {
+            self.sound_queue.push(SoundEvent::UiConfirm);
             self.curr_selection = 0; //Selection should always be the first option to start
             self.selections = vec![1,2,0].into_iter().cycle(); //Must also reset as if 0 was chosen
             self.curr_dir = Direction::Up; //Dir should always start at up
             self.directions = vec![Direction::Right, Direction::Down, Direction::Left, Direction::Up].into_iter().cycle(); //Must also reset as if up was chosen
             self.action_state = ActionType::Action;
-        } else if kb[Key::A] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
+        } else if action_pressed { self.sound_queue.push(SoundEvent::UiDeny); }
 
-        if kb[Key::E] == Pressed                     { self.action_state = ActionType::End;}
+        if self.input.pressed(controls, InputAction::EndTurn) { self.action_state = ActionType::End;}
 
         //Only accept commands when the player can do something
         match self.action_state {
             ActionType::Move => { // Default to this state so players are not forced to explicitly end and no cycles are created
-                if self.moves > 0 {
-                    if kb[Key::Up] == Pressed { moved = self.try_move(Vector::new(curr_loc.x, curr_loc.y - 1.0))?; }
-                    else if kb[Key::Left] == Pressed { moved = self.try_move(Vector::new(curr_loc.x - 1.0, curr_loc.y))?;}
-                    else if kb[Key::Down] == Pressed { moved = self.try_move(Vector::new(curr_loc.x, curr_loc.y + 1.0))?;}
-                    else if kb[Key::Right] == Pressed { moved = self.try_move(Vector::new(curr_loc.x + 1.0, curr_loc.y))?;}
-                } else {
-                    if kb[Key::Up] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
-                    else if kb[Key::Left] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
-                    else if kb[Key::Down] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
-                    else if kb[Key::Right] == Pressed { self.soft_click.execute(|music| { music.play() })?; }
+                let direction = if up { Some(Direction::Up) }
+                                else if left { Some(Direction::Left) }
+                                else if down { Some(Direction::Down) }
+                                else if right { Some(Direction::Right) }
+                                else { None };
+
+                if let Some(direction) = direction {
+                    if moves == 0 || !self.submit(Command::Move(direction))? { self.sound_queue.push(SoundEvent::UiDeny); }
                 }
             },
             ActionType::Action => {
-                if self.actions > 0 {
-                    if kb[Key::Up] == Pressed { self.prev_selection()?; } //Action selection
-                    else if kb[Key::Down] == Pressed { self.next_selection()?;}
-
-                    if kb[Key::Left] == Pressed { self.prev_direction()?; } //Direction changing
-                    else if kb[Key::Right] == Pressed { self.next_direction()?;}
-
-                    if kb[Key::Return] == Pressed {
-                        //Check to see if a player is allowed to use the selected ability and use it if so
-                        if self.player_ref[self.curr_player].can_act(self.curr_selection + 1, &self.game_board, &self.player_ref)? {
-                            self.click.execute(|music| { music.play() })?;
-                            let current_player = &self.player_ref[self.curr_player];
-                            let target_action = current_player.act(self.curr_selection + 1, self.curr_dir, &self.game_board, &self.player_ref)?;
-                            self.execute_action(target_action.0, target_action.1)?;
-                            self.actions -= 1;
-                        } else { self.soft_click.execute(|music| { music.play() })?; }
+                if actions > 0 {
+                    //Action selection, gamepads cycle with their shoulder buttons
+                    if self.input.key_pressed(controls, InputAction::Up) || controls.button_pressed(GamepadButton::ShoulderLeft) {
+                        self.prev_selection()?;
+                    } else if self.input.key_pressed(controls, InputAction::Down) || controls.button_pressed(GamepadButton::ShoulderRight) {
+                        self.next_selection()?;
+                    }
+
+                    //Direction changing, keys turn the aim while the D-pad points it
+                    if self.input.key_pressed(controls, InputAction::Left) { self.prev_direction()?; }
+                    else if self.input.key_pressed(controls, InputAction::Right) { self.next_direction()?; }
+                    else if controls.button_pressed(GamepadButton::DpadUp) { self.aim(Direction::Up); }
+                    else if controls.button_pressed(GamepadButton::DpadRight) { self.aim(Direction::Right); }
+                    else if controls.button_pressed(GamepadButton::DpadDown) { self.aim(Direction::Down); }
+                    else if controls.button_pressed(GamepadButton::DpadLeft) { self.aim(Direction::Left); }
+
+                    //The match checks the player is allowed to use the selected ability
+                    if self.input.pressed(controls, InputAction::Confirm) &&
+                        !self.submit(Command::Act(self.curr_selection + 1, self.curr_dir))? {
+                        self.sound_queue.push(SoundEvent::UiDeny);
                     }
                 } else { //Being in the action state with no actions is nonsensical and forbidden
                     self.action_state = ActionType::Move;
                 }
             },
-            ActionType::End => {
-                self.end_flag = true;
-                self.next_turn()?;
+            ActionType::End => { //Ended once, the state stays here until the next turn begins
+                if !self.end_flag {
+                    self.end_flag = true;
+                    self.submit(Command::EndTurn)?;
+                }
             }
         }
 
-        if moved {
-            moved = false;
-            self.moves -= 1;
-        }
-        if acted {
-            acted = false;
-            self.actions -= 1;
+        Ok(())
+    }
+
+    /// Processes input for the pause menu
+    fn update_pause(&mut self, window: &mut Window) -> Result<SceneReturn> {
+        let mut retval = SceneReturn::Good;
+        let controls = self.controls(window)?;
+        let item = PAUSE_ITEMS[self.pause_selection];
+
+        if self.confirming {
+            if self.input.pressed(&controls, InputAction::Confirm) {
+                self.sound_queue.push(SoundEvent::UiConfirm);
+                retval = self.choose(item)?;
+            } else if self.input.pressed(&controls, InputAction::Cancel) {
+                self.sound_queue.push(SoundEvent::UiDeny);
+                self.confirming = false;
+            }
+            return Ok(retval);
         }
 
-        retval = self.check_game()?;
+        if self.input.pressed(&controls, InputAction::Up) && self.pause_selection > 0 {
+            self.pause_selection -= 1;
+        } else if self.input.pressed(&controls, InputAction::Down) && self.pause_selection < PAUSE_ITEMS.len() - 1 {
+            self.pause_selection += 1;
+        }
 
-        if kb[Key::Key0] == Pressed {
-            self.winner = PlayerType::Undetermined;
-            retval = SceneReturn::Finished;
+        if self.input.pressed(&controls, InputAction::Confirm) {
+            self.sound_queue.push(SoundEvent::UiConfirm);
+            if item.needs_confirm() { self.confirming = true; } else { retval = self.choose(item)?; }
+        } else if self.input.pressed(&controls, InputAction::Cancel) {
+            self.sound_queue.push(SoundEvent::UiConfirm);
+            self.paused = false;
         }
 
         Ok(retval)
     }
 
+    /// Opens the pause menu with `item` selected
+    fn pause(&mut self, item: PauseItem) {
+        self.paused = true;
+        self.confirming = false;
+        self.pause_selection = PAUSE_ITEMS.iter().position(|choice| *choice == item).expect("Every item is in the pause menu");
+    }
+
+    /// Carries out a pause menu choice, confirmation has already been given if it needed one
+    /// Conceding hands the win to the other team of the one on this screen, so the outro shows the right victory
+    /// Only the host can restart a networked match, a match played by mail cannot be restarted and spectators can
+    /// neither restart nor concede
+    fn choose(&mut self, item: PauseItem) -> Result<SceneReturn> {
+        self.confirming = false;
+        let retval = match item {
+            PauseItem::Resume   => { self.paused = false; SceneReturn::Good },
+            PauseItem::Restart | PauseItem::Concede if self.watching() => {
+                self.sound_queue.push(SoundEvent::UiDeny);
+                SceneReturn::Good
+            },
+            PauseItem::Restart if matches!(self.connection, Some(Connection::Client(_)) | Some(Connection::Mail(_))) => {
+                self.sound_queue.push(SoundEvent::UiDeny);
+                SceneReturn::Good
+            },
+            PauseItem::Restart  => {
+                self.config.seed = rand::thread_rng().gen(); //A restart rolls its own damage and environment
+                self.reset()?;
+                SceneReturn::Good
+            },
+            PauseItem::Concede  => {
+                self.paused = false;
+                if !self.submit(Command::Concede(self.viewer()?))? { self.sound_queue.push(SoundEvent::UiDeny); }
+                self.check_game()?
+            },
+            //Stay paused so closing settings comes back to this menu
+            PauseItem::Settings => SceneReturn::Settings,
+            PauseItem::Quit     => {
+                self.paused = false;
+                SceneReturn::Title
+            },
+        };
+        Ok(retval)
+    }
+
+    /// Returns the controls for the current turn, only the current team's gamepad can play it
+    /// When settings leave both teams on any pad and two are connected, the first human's team takes the first pad
+    fn controls<'a>(&self, window: &'a Window) -> Result<Controls<'a>> {
+        let team = self.game.team()?;
+        let unassigned = self.elder_god_pad == PadSlot::Any && self.hunters_pad == PadSlot::Any;
+        let slot = match team {
+            _ if unassigned && window.gamepads().len() >= 2 => {
+                if team == self.config.player_one { PadSlot::Slot(0) } else { PadSlot::Slot(1) }
+            },
+            PlayerType::Player1 => self.elder_god_pad,
+            PlayerType::Player2 => self.hunters_pad,
+            _                   => PadSlot::Any,
+        };
+        Ok(Controls::new(window, slot))
+    }
+
     /// Draw stuff on the screen
     pub fn draw(&mut self, window: &mut Window) -> Result<()> {
         let window_center = Vector::new(window.screen_size().x as i32 / 2, window.screen_size().y as i32 / 2);
-        //This must be called to ensure that 'anim_key' is always the correct key of the animation to draw
-        let anim_key = self.selectable_animator.next_if_not(window.current_fps())?.as_str();
+        let anim_key = self.selectable_animator.current_key();
 
         // Draw the frame and overlay
         draw_ex_with_center(window, &mut self.game_background, window_center, Transform::IDENTITY, 1.0)?;
         draw_ex_with_center(window, &mut self.game_overlay, window_center, Transform::IDENTITY, 2.0)?;
 
+        let board = self.game.board();
+        let players = self.game.entities();
+        let mut curr = *self.game.current_entity();
+        let (moves, actions) = (self.game.moves(), self.game.actions());
+
+        //In fog of war the board is seen through the eyes of the team on this screen
+        let curr_team = self.viewer()?;
+        let vision = self.game.vision(curr_team)?;
+        let seen = |pos: Vector| vision.as_ref().is_none_or(|vision| field_of_view::sees(vision, Coord::from(pos)));
+
         // Draw GameBoard, calculates coordinates from the center for a 19x15 board of 40x40 pixels
-        for row in self.game_board.get_board()? {
-            for cell in row {
-                let tile_key = cell.get_land()?.key().expect("No known key for tile.");;
-                let cond_key = cell.get_cond()?.key().expect("No known key for tile.");
-                let pos = cell.get_pos().expect("Failed to get cell position game::draw");
-
-                //Draw land
-                draw_ex_atlas_with_center(window, &mut self.game_tiles,
-                                       Vector::new(window_center.x - 380.0 + (40.0 * pos.x) + 20.0,
-                                                   window_center.y - 300.0 + (40.0 * pos.y) + 20.0),
-                                          Transform::IDENTITY, 3.0, tile_key)?;
-
-                //Draw conditions at layer 2
-                draw_ex_atlas_with_center(window, &mut self.token_tiles,
-                                       Vector::new(window_center.x - 380.0 + (40.0 * pos.x) + 23.0,
-                                                   window_center.y - 300.0 + (40.0 * pos.y) + 18.0),
-                                            Transform::IDENTITY, 6.0,cond_key)?;
+        for cell in board.cells() {
+            let tile_key = cell.get_land()?.key().expect("No known key for tile.");
+            let cond_key = cell.get_cond()?.key().expect("No known key for tile.");
+            let pos = cell.get_pos().expect("Failed to get cell position game::draw");
+
+            //Draw land
+            draw_ex_atlas_with_center(window, &mut self.game_tiles,
+                                   Vector::new(window_center.x - 380.0 + (40.0 * pos.x) + 20.0,
+                                               window_center.y - 300.0 + (40.0 * pos.y) + 20.0),
+                                      Transform::IDENTITY, 3.0, tile_key)?;
+
+            //Draw conditions at layer 2
+            draw_ex_atlas_with_center(window, &mut self.token_tiles,
+                                   Vector::new(window_center.x - 380.0 + (40.0 * pos.x) + 23.0,
+                                               window_center.y - 300.0 + (40.0 * pos.y) + 18.0),
+                                        Transform::IDENTITY, 6.0,cond_key)?;
+
+            //Dim cells the team cannot see, over the tokens that would be there
+            if !seen(pos) {
+                let fog = Rectangle::new(Vector::new(window_center.x - 380.0 + (40.0 * pos.x), window_center.y - 300.0 + (40.0 * pos.y)), (40.0, 40.0));
+                window.draw_ex(&fog, Col(Color::BLACK.with_alpha(0.6)), Transform::IDENTITY, 6.5);
             }
         }
 
@@ -467,18 +670,40 @@ impl ElderGame {
         }
         
         //Draw Selected Player Class Label
-        let curr_class_key = self.player_ref[self.curr_player].get_class()?.key().to_owned() + "Class";
+        let curr_class_key = curr.get_class()?.key().to_owned() + "Class";
         draw_ex_atlas_with_center(window, &mut self.token_tiles,
                                   Vector::new(window_center.x - 303.0, window_center.y - 171.0),
                                   Transform::IDENTITY, 6.1, &curr_class_key[..])?;
 
-        //Draw Players
-        for player in self.player_ref.iter() {
-            let player_pos = player.get_pos()?;
+        //Draw Players, moving tokens are drawn at their tweened position
+        //The dead are not drawn, invisible or unseen entities are only shown to their own team and downed hunters are shaded
+        for (index, player) in players.iter().enumerate() {
+            if player.is_dead() { continue; }
+            if self.game.hidden_from(player, curr_team, vision.as_deref())? { continue; }
+            let player_pos = match self.token_motion.iter().find(|(moving, _, _)| *moving == index) {
+                Some((_, _, tween)) => tween.value(),
+                None => player.get_pos()?,
+            };
             let player_key = player.get_class()?.key();
-            draw_ex_atlas_with_center(window, &mut self.token_tiles,
-                                   Vector::new(window_center.x - 380.0 + (40.0 * player_pos.x) + 23.0,
-                                               window_center.y - 300.0 + (40.0 * player_pos.y) + 18.0), Transform::IDENTITY, 5.0, player_key)?;
+            let token_center = Vector::new(window_center.x - 380.0 + (40.0 * player_pos.x) + 23.0,
+                                           window_center.y - 300.0 + (40.0 * player_pos.y) + 18.0);
+            draw_ex_atlas_with_center(window, &mut self.token_tiles, token_center, Transform::IDENTITY, 5.0, player_key)?;
+            if player.get_status()? == Status::Downed {
+                let shade = Rectangle::new(token_center - Vector::new(15.0, 15.0), (30.0, 30.0));
+                window.draw_ex(&shade, Col(self.palette.hit().with_alpha(0.5)), Transform::IDENTITY, 5.1);
+            }
+        }
+
+        //Draw the monster's signs for the hunters
+        if curr_team == PlayerType::Player2 {
+            for (pos, sign, _) in self.game.signs().iter() {
+                let sign_center = Vector::new(window_center.x - 380.0 + (40.0 * pos.x) + 20.0,
+                                              window_center.y - 300.0 + (40.0 * pos.y) + 20.0);
+                match sign {
+                    Sign::Track => window.draw_ex(&Circle::new(sign_center, 5.0), Col(Color::BLACK.with_alpha(0.6)), Transform::IDENTITY, 5.5),
+                    Sign::Noise => window.draw_ex(&Circle::new(sign_center, 14.0), Col(self.palette.hit().with_alpha(0.4)), Transform::IDENTITY, 5.5),
+                }
+            }
         }
 
         // Draw Menu Labels
@@ -492,15 +717,15 @@ impl ElderGame {
                             Transform::IDENTITY, 8.04)?;
 
         //Get Player Info and calculate current bar size
-        let player_team = *self.player_ref[self.curr_player].get_player()?;
+        let player_team = self.game.team()?;
 
-        let player_max_hp = *self.player_ref[self.curr_player].get_stats()?.get_hp() as f32;
-        let player_hp = *self.player_ref[self.curr_player].get_curr_stats()?.get_hp() as f32;
+        let player_max_hp = *curr.get_stats()?.get_hp() as f32;
+        let player_hp = *curr.get_curr_stats()?.get_hp() as f32;
         let full_hp_px = 85.0;
         let curr_hp_px: f32 = (player_hp / player_max_hp) * full_hp_px;
 
-        let player_max_moves = *self.player_ref[self.curr_player].get_stats()?.get_speed() as f32;
-        let player_moves = self.moves as f32;
+        let player_max_moves = *curr.get_stats()?.get_speed() * FULL_MOVE as f32;
+        let player_moves = moves as f32;
         let full_mv_px = 55.0;
         let curr_mv_px: f32 = (player_moves / player_max_moves) * full_mv_px;
 
@@ -526,12 +751,12 @@ impl ElderGame {
         //Draw Move and HP meters
         let hp_bar = Rectangle::new(Vector::new(window_center.x + 282.0, window_center.y - 208.0), (full_hp_px, 13.0));
         let curr_hp_bar = Rectangle::new(Vector::new(window_center.x + 282.0, window_center.y - 208.0), (curr_hp_px, 13.0));
-        window.draw_ex(&hp_bar, Col(Color::RED.with_alpha(0.5)), Transform::IDENTITY, 8.046);
-        window.draw_ex(&curr_hp_bar, Col(Color::RED), Transform::IDENTITY, 8.047);
+        window.draw_ex(&hp_bar, Col(self.palette.hp().with_alpha(0.5)), Transform::IDENTITY, 8.046);
+        window.draw_ex(&curr_hp_bar, Col(self.palette.hp()), Transform::IDENTITY, 8.047);
         let move_bar = Rectangle::new(Vector::new(window_center.x + 310.0, window_center.y - 178.0), (full_mv_px, 13.0));
         let curr_move_bar = Rectangle::new(Vector::new(window_center.x + 310.0, window_center.y - 178.0), (curr_mv_px, 13.0));
-        window.draw_ex(&move_bar, Col(Color::BLUE.with_alpha(0.5)), Transform::IDENTITY, 8.047);
-        window.draw_ex(&curr_move_bar, Col(Color::BLUE), Transform::IDENTITY, 8.048);
+        window.draw_ex(&move_bar, Col(self.palette.moves().with_alpha(0.5)), Transform::IDENTITY, 8.047);
+        window.draw_ex(&curr_move_bar, Col(self.palette.moves()), Transform::IDENTITY, 8.048);
 
         // Draw State Indicator
         let y_offset = match self.action_state {
@@ -546,8 +771,8 @@ impl ElderGame {
         let mut move_text = &mut self.move_grey;
         let mut action_text = &mut self.action_grey;
         let mut end_text = &mut self.end_grey;
-        if self.moves > 0   { move_text = &mut self.move_text; }
-        if self.actions > 0 { action_text = &mut self.action_text; }
+        if moves > 0   { move_text = &mut self.move_text; }
+        if actions > 0 { action_text = &mut self.action_text; }
         if !self.end_flag   { end_text = &mut self.end_text; }
         // Draw label text items, should always render on top to show the state the game is in
         draw_ex_with_center(window, move_text, Vector::new(window_center.x - 303.0, window_center.y + 175.0), Transform::IDENTITY, 8.11)?;
@@ -559,45 +784,45 @@ impl ElderGame {
         let mut action_2;
         let mut action_3;
         //Decide which actions can be taken
-        match self.player_ref[self.curr_player].get_class()? {
+        match curr.get_class()? {
             ClassType::Support  => {
                 action_1 = &mut self.bio_grey;
                 action_2 = &mut self.shield_grey;
                 action_3 = &mut self.renew_grey;
-                if self.actions > 0 {
-                    if self.player_ref[self.curr_player].can_act(1, &self.game_board, &self.player_ref)? { action_1 = &mut self.bio_white;}
-                    if self.player_ref[self.curr_player].can_act(2, &self.game_board, &self.player_ref)? { action_2 = &mut self.shield_white; }
-                    if self.player_ref[self.curr_player].can_act(3, &self.game_board, &self.player_ref)? { action_3 = &mut self.renew_white; }
+                if actions > 0 {
+                    if curr.can_act(1, board, players)? { action_1 = &mut self.bio_white;}
+                    if curr.can_act(2, board, players)? { action_2 = &mut self.shield_white; }
+                    if curr.can_act(3, board, players)? { action_3 = &mut self.renew_white; }
                 }
             },
             ClassType::Assault  => {
                 action_1 = &mut self.pierce_grey;
                 action_2 = &mut self.grenade_grey;
                 action_3 = &mut self.airraid_grey;
-                if self.actions > 0 {
-                    if self.player_ref[self.curr_player].can_act(1, &self.game_board, &self.player_ref)? { action_1 = &mut self.pierce_white;}
-                    if self.player_ref[self.curr_player].can_act(2, &self.game_board, &self.player_ref)? { action_2 = &mut self.grenade_white; }
-                    if self.player_ref[self.curr_player].can_act(3, &self.game_board, &self.player_ref)? { action_3 = &mut self.airraid_white; }
+                if actions > 0 {
+                    if curr.can_act(1, board, players)? { action_1 = &mut self.pierce_white;}
+                    if curr.can_act(2, board, players)? { action_2 = &mut self.grenade_white; }
+                    if curr.can_act(3, board, players)? { action_3 = &mut self.airraid_white; }
                 }
             },
             ClassType::Trapper  => {
                 action_1 = &mut self.caltrop_grey;
                 action_2 = &mut self.spear_grey;
                 action_3 = &mut self.cage_grey;
-                if self.actions > 0 {
-                    if self.player_ref[self.curr_player].can_act(1, &self.game_board, &self.player_ref)? { action_1 = &mut self.caltrop_white;}
-                    if self.player_ref[self.curr_player].can_act(2, &self.game_board, &self.player_ref)? { action_2 = &mut self.spear_white; }
-                    if self.player_ref[self.curr_player].can_act(3, &self.game_board, &self.player_ref)? { action_3 = &mut self.cage_white; }
+                if actions > 0 {
+                    if curr.can_act(1, board, players)? { action_1 = &mut self.caltrop_white;}
+                    if curr.can_act(2, board, players)? { action_2 = &mut self.spear_white; }
+                    if curr.can_act(3, board, players)? { action_3 = &mut self.cage_white; }
                 }
             },
-            ClassType::Wraith   => {
+            ClassType::Wraith | ClassType::Kraken | ClassType::Elder => {
                 action_1 = &mut self.drain_grey;
                 action_2 = &mut self.decoy_grey;
                 action_3 = &mut self.rend_grey;
-                if self.actions > 0 {
-                    if self.player_ref[self.curr_player].can_act(1, &self.game_board, &self.player_ref)? { action_1 = &mut self.drain_white;}
-                    if self.player_ref[self.curr_player].can_act(2, &self.game_board, &self.player_ref)? { action_2 = &mut self.decoy_white; }
-                    if self.player_ref[self.curr_player].can_act(3, &self.game_board, &self.player_ref)? { action_3 = &mut self.rend_white; }
+                if actions > 0 {
+                    if curr.can_act(1, board, players)? { action_1 = &mut self.drain_white;}
+                    if curr.can_act(2, board, players)? { action_2 = &mut self.decoy_white; }
+                    if curr.can_act(3, board, players)? { action_3 = &mut self.rend_white; }
                 }
             },
             _c                   => { panic!("Attempted to render unsupported class abilities, {:?}", _c) }
@@ -619,8 +844,9 @@ impl ElderGame {
                                 Transform::IDENTITY, 8.05)?;
         }
 
-        //Draw appropriate general help text
+        //Draw appropriate general help text, spectators are told who they follow instead
         let help = match self.action_state{
+            _ if self.watching() => {&mut self.watch_help[if curr_team == PlayerType::Player1 { 0 } else { 1 }]},
             ActionType::Move => {&mut self.move_help},
             ActionType::Action => {&mut self.action_help},
             ActionType::End => {&mut self.end_help}
@@ -628,10 +854,20 @@ impl ElderGame {
         draw_ex_with_center(window, help, Vector::new(window_center.x, window_center.y + 286.0),
                                              Transform::IDENTITY, 8.4)?;
 
-        //Draw action help text
-        if self.action_state == ActionType::Action {
+        //Draw action help text, unless a networked match is waiting for the other screen
+        let waiting = self.connection.as_ref().is_some_and(|connection| !connection.connected());
+        let mailing = matches!(self.connection, Some(Connection::Mail(_))) && player_team != curr_team &&
+            self.game.winner() == PlayerType::Undetermined;
+        if waiting {
+            draw_ex_with_center(window, &mut self.waiting_text, Vector::new(window_center.x, window_center.y - 286.0),
+                                Transform::IDENTITY, 8.41)?;
+        } else if mailing {
+            let mail_text = self.mail_refusal.as_mut().map_or(&mut self.mail_text, |(_, refusal)| refusal);
+            draw_ex_with_center(window, mail_text, Vector::new(window_center.x, window_center.y - 286.0),
+                                Transform::IDENTITY, 8.41)?;
+        } else if self.action_state == ActionType::Action {
             //Decide which help text to render
-            let action_help = match self.player_ref[self.curr_player].get_class()? {
+            let action_help = match curr.get_class()? {
                 ClassType::Support  => {
                     match self.curr_selection {
                         0 => &mut self.bio_help,
@@ -656,7 +892,7 @@ impl ElderGame {
                         _ => panic!("Tried to draw invalid ability.")
                     }
                 },
-                ClassType::Wraith   => {
+                ClassType::Wraith | ClassType::Kraken | ClassType::Elder => {
                     match self.curr_selection {
                         0 => &mut self.drain_help,
                         1 => &mut self.decoy_help,
@@ -670,40 +906,50 @@ impl ElderGame {
                                 Transform::IDENTITY, 8.41)?;
         }
 
+        //Draw the cells the player can still move to
+        if self.action_state == ActionType::Move && moves > 0 && !self.paused {
+            let reachable = curr.reachable(moves, board, players)?;
+            for coordinate in reachable {
+                draw_ex_atlas_with_center(window, &mut self.token_tiles,
+                                          Vector::new(window_center.x - 380.0 + (40.0 * coordinate.x) + 23.0,
+                                                      window_center.y - 300.0 + (40.0 * coordinate.y) + 18.0), Transform::IDENTITY, 8.5, anim_key)?;
+            }
+        }
+
         //Draw selectable animation
         // This targeting logic must match targeting logic used in entities targeting logic to be correct
         if self.action_state == ActionType::Action {
             //Decide which tiles to put the animation on
-            let selectable_coordinates = match self.player_ref[self.curr_player].get_class()? {
+            let selectable_coordinates = match curr.get_class()? {
                 ClassType::Support  => {
                     match self.curr_selection {
-                        0 => self.player_ref[self.curr_player].adjacent_radial(3, &self.game_board, &self.player_ref)?,
-                        1 => self.player_ref[self.curr_player].list_range_ally(&self.game_board, &self.player_ref)?,
-                        2 => self.player_ref[self.curr_player].adjacent_radial(2, &self.game_board, &self.player_ref)?,
+                        0 => curr.adjacent_radial(3, board, players)?,
+                        1 => curr.list_range_ally(board, players)?,
+                        2 => curr.adjacent_radial(2, board, players)?,
                         _ => panic!("Tried to draw invalid ability.")
                     }
                 },
                 ClassType::Assault  => {
                     match self.curr_selection {
-                        0 => self.player_ref[self.curr_player].directed_line_range(3, self.curr_dir, &self.game_board, &self.player_ref)?,
-                        1 => self.player_ref[self.curr_player].directed_line_radial_cast(3, 1, self.curr_dir, &self.game_board, &self.player_ref)?,
-                        2 => self.player_ref[self.curr_player].directed_line_radial(3, 3, self.curr_dir, &self.game_board, &self.player_ref)?,
+                        0 => curr.directed_line_range(3, self.curr_dir, board, players)?,
+                        1 => curr.directed_line_radial_cast(3, 1, self.curr_dir, board, players)?,
+                        2 => curr.directed_line_radial_sky(3, 3, self.curr_dir, board, players)?,
                         _ => panic!("Tried to draw invalid ability.")
                     }
                 },
                 ClassType::Trapper  => {
                     match self.curr_selection {
-                        0 => self.player_ref[self.curr_player].directed_line_radial(1, 1, self.curr_dir, &self.game_board, &self.player_ref)?,
-                        1 => self.player_ref[self.curr_player].directed_line_cast(6, self.curr_dir, &self.game_board, &self.player_ref)?,
-                        2 => self.player_ref[self.curr_player].adjacent_shell(3,  &self.game_board, &self.player_ref)?,
+                        0 => curr.directed_line_radial(1, 1, self.curr_dir, board, players)?,
+                        1 => curr.directed_line_cast(6, self.curr_dir, board, players)?,
+                        2 => curr.adjacent_shell(3,  board, players)?,
                         _ => panic!("Tried to draw invalid ability.")
                     }
                 },
-                ClassType::Wraith   => {
+                ClassType::Wraith | ClassType::Kraken | ClassType::Elder => {
                     match self.curr_selection {
-                        0 => self.player_ref[self.curr_player].adjacent_range(1, &self.game_board, &self.player_ref)?,
-                        1 => self.player_ref[self.curr_player].adjacent_range(1, &self.game_board, &self.player_ref)?,
-                        2 => self.player_ref[self.curr_player].directed_line_radial(1, 1,self.curr_dir, &self.game_board, &self.player_ref)?,
+                        0 => curr.adjacent_range(1, board, players)?,
+                        1 => curr.adjacent_range(1, board, players)?,
+                        2 => curr.directed_line_radial(1, 1,self.curr_dir, board, players)?,
                         _ => panic!("Tried to draw invalid ability.")
                     }
                 },
@@ -718,6 +964,51 @@ impl ElderGame {
 
         }
 
+        //Draw one-shot effects over the cells they affect
+        for (coordinate, _, effect) in self.effects.iter() {
+            draw_ex_atlas_with_center(window, &mut self.token_tiles,
+                                      Vector::new(window_center.x - 380.0 + (40.0 * coordinate.x) + 23.0,
+                                                  window_center.y - 300.0 + (40.0 * coordinate.y) + 18.0), Transform::IDENTITY, 8.6, effect.current_key())?;
+        }
+        let board_origin = Vector::new(window_center.x - 380.0, window_center.y - 300.0);
+        for (_, effect) in self.ability_effects.iter() {
+            effect.draw(window, board_origin, 8.7);
+        }
+
+        //Cover the board entirely until the next team has the screen
+        if let Some(team) = self.handoff {
+            let cover = Rectangle::new(Vector::new(window_center.x - 400.0, window_center.y - 300.0), (800.0, 600.0));
+            window.draw_ex(&cover, Col(Color::BLACK), Transform::IDENTITY, 9.0);
+            let title = if team == PlayerType::Player1 { &mut self.handoff_titles[0] } else { &mut self.handoff_titles[1] };
+            draw_ex_with_center(window, title, Vector::new(window_center.x, window_center.y - 20.0), Transform::IDENTITY, 9.1)?;
+            draw_ex_with_center(window, &mut self.handoff_help, Vector::new(window_center.x, window_center.y + 40.0),
+                                Transform::IDENTITY, 9.1)?;
+        }
+
+        //Draw the pause menu over everything
+        if self.paused {
+            let shade = Rectangle::new(Vector::new(window_center.x - 400.0, window_center.y - 300.0), (800.0, 600.0));
+            window.draw_ex(&shade, Col(Color::BLACK.with_alpha(0.7)), Transform::IDENTITY, 9.0);
+            draw_ex_with_center(window, &mut self.pause_title, Vector::new(window_center.x, window_center.y - 150.0),
+                                Transform::IDENTITY, 9.1)?;
+
+            if self.confirming {
+                draw_ex_with_center(window, &mut self.pause_questions[self.pause_selection], window_center, Transform::IDENTITY, 9.1)?;
+                draw_ex_with_center(window, &mut self.confirm_help, Vector::new(window_center.x, window_center.y + 40.0),
+                                    Transform::IDENTITY, 9.1)?;
+            } else {
+                for (index, item) in self.pause_items.iter_mut().enumerate() {
+                    let y = window_center.y - 80.0 + 40.0 * index as f32;
+                    draw_ex_with_center(window, item, Vector::new(window_center.x, y), Transform::IDENTITY, 9.1)?;
+                    if index == self.pause_selection {
+                        draw_ex_with_center(window, &mut self.underline, Vector::new(window_center.x, y + 11.0), Transform::IDENTITY, 9.2)?;
+                    }
+                }
+                draw_ex_with_center(window, &mut self.pause_help, Vector::new(window_center.x, window_center.y + 150.0),
+                                    Transform::IDENTITY, 9.1)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -729,74 +1020,130 @@ impl ElderGame {
     /// Special function that decides who is the winner of the game
     /// This should only be defined in a scene where a winner is relevant info, like a game
     pub fn get_winner(&mut self) -> Result<PlayerType> {
-        Ok(self.winner)
+        Ok(self.game.winner())
     }
 
-    /// Shifts index to the next player's turn, and sets variables appropriately
-    /// Also apply buff and debuff affects based on player status and land condition
-    fn next_turn(&mut self) -> Result<()> {
-        self.curr_player = self.turn_order.next().expect("Cannot find next player index game::next_turn");
-        self.moves = *self.player_ref[self.curr_player].get_stats()?.get_speed() as u32;
-        self.actions = *self.player_ref[self.curr_player].get_stats()?.get_actions() as u32;
-        self.action_state = ActionType::Move;
-        self.end_flag = false;
+    /// Applies the player's preferences
+    pub fn set_preferences(&mut self, preferences: &Preferences) -> Result<()> {
+        self.animation_speed = preferences.animation_speed as f64;
+        self.palette = preferences.palette;
+        self.elder_god_pad = preferences.elder_god_pad;
+        self.hunters_pad = preferences.hunters_pad;
+        Ok(())
+    }
 
-        self.turn_start_loc = self.player_ref[self.curr_player].get_pos()?;
+    /// Applies new key bindings, re-rendering the [Press] menu and help text that name keys
+    pub fn set_input_map(&mut self, input: &InputMap) -> Result<()> {
+        if self.input == *input { return Ok(()); }
+        self.input = input.clone();
+
+        let [move_help, action_help, end_help] = ElderGame::render_key_help(&self.input);
+        self.move_help = move_help;
+        self.action_help = action_help;
+        self.end_help = end_help;
+        let [move_text, action_text, end_text] = ElderGame::render_key_menu(&self.input, Color::WHITE);
+        self.move_text = move_text;
+        self.action_text = action_text;
+        self.end_text = end_text;
+        let [move_grey, action_grey, end_grey] = ElderGame::render_key_menu(&self.input, Color::from_rgba(132, 126, 135, 255.0));
+        self.move_grey = move_grey;
+        self.action_grey = action_grey;
+        self.end_grey = end_grey;
+        let [pause_help, confirm_help, handoff_help] = ElderGame::render_pause_help(&self.input);
+        self.pause_help = pause_help;
+        self.confirm_help = confirm_help;
+        self.handoff_help = handoff_help;
+        self.watch_help = ElderGame::render_watch_help(&self.input);
 
-        //Set buffs and debuffs depending on player status
-        match self.player_ref[self.curr_player].get_status()? {
-            Status::Normal   => {/*do nothing*/},
-            Status::Crippled => { self.moves = (self.moves / 2) as u32 }, //Halve movement
-            Status::Dead => {  }
-        }
+        Ok(())
+    }
 
-        let starting_cell = &self.game_board.get_board()?[self.turn_start_loc.y as usize][self.turn_start_loc.x as usize];
-        //Set buffs and debuffs depending on land status
-        let starting_cond = starting_cell.get_cond()?;
-        match starting_cond {
-            TerrainStatus::Burning   => { //Damage players on a burning tile
-                let mut rng = rand::thread_rng();
-                let lvl = self.player_ref[self.curr_player].get_level()? as f32;
-                let dmg_pow = lvl * 5.0 + rng.gen_range(0.0, lvl);
+    /// Renders the move, action and end help text for a set of bindings
+    fn render_key_help(input: &InputMap) -> [Asset<Image>; 3] {
+        let font_mononoki = "square.ttf";
+        let name = |action| input.name(action);
+        let movement = [InputAction::Up, InputAction::Left, InputAction::Down, InputAction::Right];
+        let movement = if movement.map(|action| input.key(action)) == [Key::Up, Key::Left, Key::Down, Key::Right] {
+            "Arrow Keys".to_string()
+        } else {
+            movement.map(name).join("/")
+        };
+        let texts = [
+            format!("{} to move, {}-pause", movement, name(InputAction::Cancel)),
+            format!("{}/{}-Scroll {}/{}-Aim + {}", name(InputAction::Up), name(InputAction::Down),
+                    name(InputAction::Left), name(InputAction::Right), name(InputAction::Confirm)),
+            format!("Ending turn... {}-pause", name(InputAction::Cancel)),
+        ];
+
+        texts.map(|text| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(&text, &FontStyle::new(20.0, Color::BLACK), )})))
+    }
 
-                self.player_ref[self.curr_player].add_checked_hp(dmg_pow)?;
-            },
-            TerrainStatus::Frozen    => { self.moves = 0; }, //Can't move if frozen
-            _                        => {/*do nothing*/},
-        }
+    /// Renders the pause menu, confirmation and handoff help text for a set of bindings
+    fn render_pause_help(input: &InputMap) -> [Asset<Image>; 3] {
+        let font_mononoki = "square.ttf";
+        let texts = [
+            format!("{}/{}-Choose {}-Select {}-Resume", input.name(InputAction::Up), input.name(InputAction::Down),
+                    input.name(InputAction::Confirm), input.name(InputAction::Cancel)),
+            format!("[{}] Yes   [{}] No", input.name(InputAction::Confirm), input.name(InputAction::Cancel)),
+            format!("{}-Ready", input.name(InputAction::Confirm)),
+        ];
+
+        texts.map(|text| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(&text, &FontStyle::new(20.0, Color::WHITE), )})))
+    }
 
-        //Decrement status buff/debuff timer and gameboard land cond timers.
-        self.player_ref[self.curr_player].decrement_timer();
-        self.game_board.decrement_temp_cond_counters()?;
+    /// Renders why the awaited turn file was refused whenever the reason changes
+    fn render_mail_refusal(&mut self) {
+        let reason = match self.connection.as_ref() {
+            Some(Connection::Mail(mailbox)) => mailbox.refusal(),
+            _                               => None,
+        };
+        if reason == self.mail_refusal.as_ref().map(|(shown, _)| shown.as_str()) { return; }
 
-        Ok(())
+        let font_mononoki = "square.ttf";
+        self.mail_refusal = reason.map(|reason| {
+            let text = reason.to_string();
+            let image = Asset::new(Font::load(font_mononoki).and_then(move |font| {
+                font.render(&text, &FontStyle::new(16.0, Color::BLACK), )}));
+            (reason.to_string(), image)
+        });
     }
 
-    /// Checks for victory conditions, sets winner if needed and returns value to signal end of game
-    fn check_game(&mut self) -> Result<SceneReturn> {
-        let mut retval = SceneReturn::Good ;
-
-        for player in &mut self.player_ref {
-            match player.get_player()? {
-                PlayerType::Player1 => {
-                    if *player.get_curr_stats()?.get_hp() <= 0.0 {
-                        self.winner = PlayerType::Player1;
-                        retval = SceneReturn::Finished;
-                        break;
-                    }
-                },
-                PlayerType::Player2 => {
-                    if *player.get_curr_stats()?.get_hp() <= 0.0 {
-                        self.winner = PlayerType::Player2;
-                        retval = SceneReturn::Finished;
-                        break;
-                    }
-                },
-                _                   => {/*Do nothing*/},
-            }
-        }
+    /// Renders the spectator help text naming the team followed and the key that switches it
+    fn render_watch_help(input: &InputMap) -> [Asset<Image>; 2] {
+        let font_mononoki = "square.ttf";
+        let confirm = input.name(InputAction::Confirm);
+        let texts = [
+            format!("Watching the Elder God, {}-Follow the Hunters", confirm),
+            format!("Watching the Hunters, {}-Follow the Elder God", confirm),
+        ];
+
+        texts.map(|text| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(&text, &FontStyle::new(20.0, Color::BLACK), )})))
+    }
 
-        Ok(retval)
+    /// Renders the [Press] menu words in a colour for a set of bindings
+    fn render_key_menu(input: &InputMap, color: Color) -> [Asset<Image>; 3] {
+        let font_mononoki = "square.ttf";
+        let texts = [
+            input.menu_label(InputAction::MoveMode, "Move"),
+            input.menu_label(InputAction::ActionMode, "Action"),
+            input.menu_label(InputAction::EndTurn, "End"),
+        ];
+
+        texts.map(|text| Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(&text, &FontStyle::new(16.0, color), )})))
+    }
+
+    /// True while any Player1 entity is below 30% of its max hp, used to pick tense music
+    pub fn is_tense(&mut self) -> Result<bool> {
+        self.game.is_tense()
+    }
+
+    /// Returns Finished once the match has a winner
+    fn check_game(&self) -> Result<SceneReturn> {
+        Ok(if self.game.winner() != PlayerType::Undetermined { SceneReturn::Finished } else { SceneReturn::Good })
     }
 
     /// Selects the next ability index between 0-2 to represent the first, second, and third options
@@ -819,6 +1166,15 @@ impl ElderGame {
         Ok(())
     }
 
+    /// Points the current direction, keeping the direction cycle in step so turning continues from it
+    fn aim(&mut self, direction: Direction) {
+        let mut order = vec![Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+        let index = order.iter().position(|dir| *dir == direction).expect("Every direction is in the cycle");
+        order.rotate_left(index + 1);
+        self.curr_dir = direction;
+        self.directions = order.into_iter().cycle();
+    }
+
     /// Selects the previous direction and sets the current direction
     fn prev_direction(&mut self) -> Result<()> {
         //Iterating thrice through a 4 element cycle is equal to going backwards once
@@ -828,391 +1184,256 @@ impl ElderGame {
         Ok(())
     }
 
-    ///Resets the game
+    /// Starts a new match set up with `config`
+    pub fn start(&mut self, config: MatchConfig) -> Result<()> {
+        self.config = config;
+        self.reset()
+    }
+
+    ///Resets the game to the start of a match with the current setup
+    /// A host sends the new match to its clients, a client asks its host for the match being played instead
     pub fn reset(&mut self) -> Result<()> {
-        //Create players
-        let wraith = Entity::new_char(ClassType::Wraith, PlayerType::Player1, 1,
-                                      Vector::new(9,11), false)
-            .expect("Cannot create Wraith game::new.");
-        let support = Entity::new_char(ClassType::Support, PlayerType::Player2, 1,
-                                       Vector::new(6,4), false)
-            .expect("Cannot create Support game::new.");
-        let assault = Entity::new_char(ClassType::Assault, PlayerType::Player2, 1,
-                                       Vector::new(9,3), false)
-            .expect("Cannot create Assault game::new.");
-        let trapper = Entity::new_char(ClassType::Trapper, PlayerType::Player2, 1,
-                                       Vector::new(12,4), false)
-            .expect("Cannot create Trapper game::new.");
-
-        //Player Vector
-        let player_ref = vec![wraith, support, assault, trapper];
-        //There must be no number greater than `player_ref.len()-1`
-        let mut turn_order = vec![1,0,2,0,3].into_iter().cycle();
-        let curr_player = turn_order.next().expect("Cannot find first player");
-        //Find first player's stats
-        let moves = *player_ref[curr_player].get_stats()?.get_speed() as u32;
-        let actions = *player_ref[curr_player].get_stats()?.get_actions() as u32;
+        self.game = Match::new(self.config)?;
 
         //Setup ability selection
-        let mut selections = vec![0,1,2].into_iter().cycle();
-        let curr_selection = selections.next().expect("Cannot find first selection");
-
-        self.player_ref = player_ref;
-        self.turn_order = turn_order;
-        self.curr_player = curr_player;
-        self.moves = moves;
-        self.actions = actions;
-        self.game_board = GameBoard::new()?;
-        self.selections = selections;
-        self.curr_selection = curr_selection;
-        self.action_state = ActionType::Move;
-        self.turn_start_loc = self.player_ref[self.curr_player].get_pos()?;
+        self.selections = vec![0,1,2].into_iter().cycle();
+        self.curr_selection = self.selections.next().expect("Cannot find first selection");
+
+        //Setup direction tracker
+        self.directions = vec![Direction::Up, Direction::Right, Direction::Down, Direction::Left].into_iter().cycle();
+        self.curr_dir = self.directions.next().expect("Cannot load initial direction in scenes::game::ElderGame::reset");
+
+        self.begin_turn();
+        self.token_motion.clear();
+        self.effects.clear();
+        self.ability_effects.clear();
+        self.finished_animations.borrow_mut().clear();
+        self.paused = false;
+        self.confirming = false;
+
+        //The first human's team is played on the host's screen, a client asks for it
+        //A mailbox is opened again so it takes in the turn files already in its folder, a finished match is left behind
+        match (&self.role, self.connection.as_mut()) {
+            (Some(role), None)                    => self.connection = Some(Connection::open(role, self.config)?),
+            (_, Some(Connection::Mail(mailbox)))  => *mailbox = mailbox.reopen(self.config.player_one, &self.config)?,
+            (_, Some(Connection::Host(host)))     => host.set_local_team(self.config.player_one),
+            (_, Some(Connection::Client(client))) => client.resync(self.config),
+            (None, _)                             => {},
+        }
+        if let Some(Connection::Host(host)) = self.connection.as_mut() {
+            host.broadcast(&self.game, &[])?;
+        }
+        self.handoff = if self.config.handoff && self.connection.is_none() { Some(self.game.team()?) } else { None };
 
         Ok(())
     }
 
-    /// Tries to move a player, returns true if moved, false otherwise
-    /// Damage or hamper player if they move onto hazardous terrain
-    /// Spike damage scales with level and armor reduces damage.
-    fn try_move(&mut self, new_loc: Vector) -> Result<bool> {
-        let mut retval= false;
+    /// Puts the turn controls back to moving, ready for a new turn
+    fn begin_turn(&mut self) {
+        self.action_state = ActionType::Move;
+        self.end_flag = false;
+    }
 
-        if self.player_ref[self.curr_player].can_move(new_loc, &self.game_board, &self.player_ref)? {
-            let cell = self.game_board.get_board()?[new_loc.y as usize][new_loc.x as usize];
-            self.player_ref[self.curr_player].set_pos(new_loc)?;
+    /// Returns true if the turn being played belongs on this screen, in a networked match only the local team's do
+    fn plays_turn(&self) -> Result<bool> {
+        Ok(match &self.connection {
+            Some(connection) => connection.local_team() == Some(self.game.team()?),
+            None             => true,
+        })
+    }
 
-            //Spikes are bad for you, don't touch them
-            if *cell.get_land()? == Terrain::Spikes {
-                if self.moves > 0 && self.moves - 1 != 0 { //take away an extra movement if they will have any extra
-                    self.moves -= 1;
-                }
-                let damage = self.player_ref[self.curr_player].get_level()? as f32 * 20.0;
-                let total_dmg = self.player_ref[self.curr_player].get_curr_stats()?.armor_reduce(damage);
-                self.player_ref[self.curr_player].add_checked_hp(-total_dmg)?;
-            }
+    /// Returns true if this screen is a spectator of a networked match
+    fn watching(&self) -> bool {
+        self.connection.as_ref().is_some_and(|connection| connection.watching())
+    }
 
-            retval = true;
+    /// Returns the team the board is shown to, the local team in a networked match and otherwise the team whose turn it is
+    /// A spectator sees it through the team it follows
+    fn viewer(&self) -> Result<PlayerType> {
+        if self.watching() { return Ok(self.follow); }
+        match self.connection.as_ref().and_then(|connection| connection.local_team()) {
+            Some(team) => Ok(team),
+            None       => self.game.team(),
         }
-
-        Ok(retval)
     }
 
-}
-
-/// This impl contains Action definitions and a routing function to execute them
-/// We should be guaranteed by here to never receive out of index coordinates so we do not check for that
-/// We also assume that passed coordinates are the correct targets and do not check that either
-impl ElderGame {
-    /// Executes passed action on the targets passed
-    fn execute_action(&mut self, targets: Vec<Vector>, ability_name: ActionAbility) -> Result<()> {
-        match ability_name {
-            ActionAbility::Bio      => { self.bio(targets)? },
-            ActionAbility::Shield   => { self.shield(targets)? },
-            ActionAbility::Renew    => { self.renew(targets)? },
-            ActionAbility::Pierce   => { self.pierce(targets)? },
-            ActionAbility::Grenade  => { self.grenade(targets)? },
-            ActionAbility::Airraid  => { self.airraid(targets)? },
-            ActionAbility::Caltrop  => { self.caltrop(targets)? },
-            ActionAbility::Spear    => { self.spear(targets)? },
-            ActionAbility::Cage     => { self.cage(targets)? },
-            ActionAbility::Drain    => { self.drain(targets)? },
-            ActionAbility::Decoy    => { self.decoy(targets, self.turn_start_loc)? },
-            ActionAbility::Rend     => { self.rend(targets)? },
-        };
-
-        Ok(())
+    /// Returns the team this screen plays for, the local team in a networked match and otherwise the first human's
+    /// A spectator counts as the team it follows
+    pub fn local_team(&self) -> Result<PlayerType> {
+        if self.watching() { return Ok(self.follow); }
+        Ok(self.connection.as_ref().and_then(|connection| connection.local_team()).unwrap_or(self.config.player_one))
     }
-    //Support Class
-    /// Heals allies, damages non-allies
-    fn bio(&mut self, targets: Vec<Vector>) -> Result<()> {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let heal_pow = pow * 10.0 + rng.gen_range(0.0, pow);
-        let curr_team = *self.player_ref[self.curr_player].get_player()?;
 
-        for target in targets {
-            for player in &mut self.player_ref {
-                if target == player.get_pos()? { //If a player is on a targeted space
-                    if curr_team == *player.get_player()? { //If player is allied
-                        player.add_checked_hp(heal_pow)?;
-                    } else {  //Player is NOT allied, here we factor in armor
-                        let damage = player.get_curr_stats()?.armor_reduce(heal_pow);
-                        player.add_checked_hp(-damage)?;
-                    }
-                }
-            }
+    /// Gives a command to the match and shows what it caused, returns false if the rules refused it
+    /// A host sends the result to its clients, a client only sends the command and waits for the host's answer
+    /// By mail only the local team's turns are played, and their commands go into its next turn file
+    fn submit(&mut self, command: Command) -> Result<bool> {
+        let playable = self.plays_turn()?;
+        match self.connection.as_mut() {
+            Some(Connection::Client(client))      => return client.send(command),
+            Some(Connection::Mail(_)) if !playable => return Ok(false),
+            _                                     => {},
         }
 
-        Ok(())
-    }
+        let hp_before = self.hp_snapshot()?;
+        let cond_before = self.cond_snapshot()?;
+        let applied = self.game.apply(command)?;
+        let events = self.game.drain_events();
+        match self.connection.as_mut() {
+            Some(Connection::Host(host))              => host.broadcast(&self.game, &events)?,
+            Some(Connection::Mail(mailbox)) if applied => mailbox.record(command, &self.game)?,
+            _                                         => {},
+        }
 
-    /// Creates a shield on targets with a strength equal to the user's
-    fn shield(&mut self, targets: Vec<Vector>)  -> Result<()>  {
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
+        self.present(events)?;
+        self.queue_hp_sounds(&hp_before)?;
+        self.queue_terrain_sounds(&cond_before)?;
+        Ok(applied)
+    }
 
-        for target in targets {
-            let cell = &mut self.game_board.get_mut_board()?[target.y as usize][target.x as usize];
-            cell.cond_with_counter(TerrainStatus::Shielded, pow as u32);
+    /// Takes in what happened on the other screen, a host carries out its client's commands and a client
+    /// replaces its match with the host's, as a mailbox does with the one in each turn file it takes in
+    fn poll_network(&mut self) -> Result<()> {
+        if self.connection.is_none() { return Ok(()); }
+        let hp_before = self.hp_snapshot()?;
+        let cond_before = self.cond_snapshot()?;
+
+        let mut events = vec![];
+        let received = match self.connection.as_mut() {
+            Some(Connection::Host(host))     => { events = host.poll(&mut self.game)?; None },
+            Some(Connection::Client(client)) => client.poll()?,
+            Some(Connection::Mail(mailbox))  => mailbox.poll(&self.game)?,
+            None                             => None,
+        };
+        self.render_mail_refusal();
+        if let Some((game, happened)) = received {
+            //A client that reconnects misses the turns that passed, so the controls start over
+            let turn_passed = game.current() != self.game.current() || game.round() != self.game.round();
+            self.game = game;
+            events = happened;
+            if turn_passed { self.begin_turn(); }
         }
 
+        self.present(events)?;
+        self.queue_hp_sounds(&hp_before)?;
+        self.queue_terrain_sounds(&cond_before)?;
         Ok(())
     }
 
-    /// Revives targets with a small amount of their max hp and heals status ailments
-    fn renew(&mut self, targets: Vec<Vector>)   -> Result<()>  {
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-
-        for target in targets {
-            for player in &mut self.player_ref {
-                if target == player.get_pos()? { //If a player is on a targeted space
-                    if player.get_status()? == Status::Dead {
-
-                        let mut threshold = pow * 2.0; //If hp remaining is below this we restore up to here, this value is between 10-100%
-                        if threshold > 100.0 { threshold = 100.0; }
-                        else if threshold < 10.0 { threshold = 10.0; }
-
-                        let max_hp = *player.get_stats()?.get_hp();
-                        let curr_hp = *player.get_curr_stats()?.get_hp();
-                        let remaining: f32 = (curr_hp / max_hp) * 100.0; //here we have a % of max hp
-
-                        if remaining < threshold { //The min hp value is not met
-                            player.add_checked_hp((max_hp * threshold) - curr_hp)?;
+    /// Animates and plays what the match reports happened
+    /// Damage, deaths and terrain changes have many causes so they are heard by comparing snapshots instead
+    fn present(&mut self, events: Vec<MatchEvent>) -> Result<()> {
+        for event in events {
+            match event {
+                MatchEvent::Moved { entity, from, to }      => self.slide_token(entity, from, to, 0.15)?,
+                MatchEvent::Teleported { entity, from, to } => self.slide_token(entity, from, to, 0.4)?,
+                MatchEvent::Hazard { .. }                   => self.sound_queue.push(SoundEvent::SpikeStep),
+                MatchEvent::Ability { ability, caster, affected } => {
+                    //Plays the ability's effect over the affected cells, falling back to a flash when it has no dedicated effect
+                    self.sound_queue.push(SoundEvent::Ability(ability));
+                    let ability_effects = effects::for_ability(ability, caster, &affected, self.palette)?;
+                    if ability_effects.is_empty() {
+                        self.flash_targets(&affected)?;
+                    } else {
+                        for effect in ability_effects {
+                            let (id, callback) = self.completion();
+                            self.ability_effects.push((id, effect.with_callback(callback)));
                         }
                     }
-
-                    player.reset_status();
-                }
+                },
+                MatchEvent::TurnChanged { from, to } => {
+                    self.sound_queue.push(SoundEvent::TurnChange);
+                    self.begin_turn();
+                    //In handoff mode the screen is covered whenever the other team takes over
+                    if self.game.config().handoff && self.connection.is_none() && from != to { self.handoff = Some(to); }
+                },
             }
         }
-
         Ok(())
     }
 
-    //Assault Class
-    /// Shoots a piercing shot that damages everything it hits that doesn't have a shield
-    /// Including shields and frozen tiles
-    fn pierce(&mut self, targets: Vec<Vector>)  -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let dmg_pow = pow * 10.0 + rng.gen_range(0.0, pow);
-
-        //Damage everything hit
-        for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    player.add_checked_hp(-damage)?;
-                }
-            }
-            //Check for TerrainStatus and decrement if hit
-            match cond {
-                TerrainStatus::Shielded => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                TerrainStatus::Frozen   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                _                       => {/*Ignore other types*/}
-            }
-
-        }
-        Ok(())
+    /// Returns an id for a new one-shot animation and the completion callback that reports it
+    fn completion(&mut self) -> (u64, Callback) {
+        let id = self.next_animation;
+        self.next_animation += 1;
+        let finished = Rc::clone(&self.finished_animations);
+        (id, Box::new(move || finished.borrow_mut().push(id)))
     }
 
-    /// Deals damage, melts frozen tiles, ignites unshielded tiles, intensifies already burning tiles
-    fn grenade(&mut self, targets: Vec<Vector>)     -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let dmg_pow = pow * 10.0 + rng.gen_range(0.0, pow);
-
-        //Damage everything hit
-        for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    player.add_checked_hp(-damage)?;
-                }
-            }
-            //Check for TerrainStatus and decrement if hit
-            match cond {
-                TerrainStatus::Shielded => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                TerrainStatus::Frozen   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].cond_with_counter(TerrainStatus::Normal, 0); },
-                TerrainStatus::Burning  => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].inc_counter(); },
-                TerrainStatus::Normal   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].cond_with_counter(TerrainStatus::Burning, pow as u32); },
-                _                       => {/*Ignore other types*/}
-            }
-
-        }
-        Ok(())
+    /// Advances every running animation by `delta` seconds and drops the one-shots whose callbacks reported them finished
+    fn animate(&mut self, delta: f64) {
+        self.selectable_animator.update(delta);
+        for (_, _, tween) in self.token_motion.iter_mut() { tween.update(delta); }
+        for (_, _, effect) in self.effects.iter_mut() { effect.update(delta); }
+        for (_, effect) in self.ability_effects.iter_mut() { effect.update(delta); }
+
+        let finished: Vec<u64> = self.finished_animations.borrow_mut().drain(..).collect();
+        if finished.is_empty() { return; }
+        self.token_motion.retain(|(_, id, _)| !finished.contains(id));
+        self.effects.retain(|(_, id, _)| !finished.contains(id));
+        self.ability_effects.retain(|(id, _)| !finished.contains(id));
     }
 
-    /// Deals serious damage to random tiles within selected area
-    fn airraid(&mut self, targets: Vec<Vector>)     -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let dmg_pow = pow * 30.0 + rng.gen_range(0.0, pow * 2.0);
-
-        let chance_hit = rng.gen_range(33.3, 66.6);
-
-        //Damage everything hit, destroy all shields, and destroy land
-        for target in targets {
-            if !rng.gen_bool(chance_hit) { continue; } // If the attack doesn't hit we skip the target
-
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    player.add_checked_hp(-damage)?;
-                }
-            }
-            //Check for TerrainStatus, destroy shields if present, and destroy all lands present
-            match cond {
-                TerrainStatus::Shielded => {
-                    self.game_board.get_mut_board()?[target.y as usize][target.x as usize].cond_with_counter(TerrainStatus::Normal, 0);
-                    self.game_board.get_mut_board()?[target.y as usize][target.x as usize].set_land(Terrain::Destroyed);
-                },
-                _ => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].set_land(Terrain::Destroyed); }
-            }
-        }
-
-        Ok(())
+    /// Returns the current hp of every entity so changes can be heard afterwards
+    fn hp_snapshot(&self) -> Result<Vec<f32>> {
+        let mut hp = vec![];
+        for player in self.game.entities() { hp.push(*player.clone().get_curr_stats()?.get_hp()); }
+        Ok(hp)
     }
 
-    //Trapper Class
-    /// Sets all unshielded land that are plains, destroyed, or roads to spiked land
-    fn caltrop(&mut self, targets: Vec<Vector>)  -> Result<()>  {
-        for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-            let land = *cell.get_land()?;
-
-            if cond != TerrainStatus::Shielded &&
-                (land == Terrain::Plain || land == Terrain::Destroyed || land == Terrain::Road)
-            {
-                self.game_board.get_mut_board()?[target.y as usize][target.x as usize].set_land(Terrain::Spikes);
-            }
-
+    /// Queues damage and death sounds for every entity that lost hp since `before` was taken
+    fn queue_hp_sounds(&mut self, before: &[f32]) -> Result<()> {
+        let after = self.hp_snapshot()?;
+        for (old, new) in before.iter().zip(after.iter()) {
+            if *new <= 0.0 && *old > 0.0 { self.sound_queue.push(SoundEvent::Death); }
+            else if new < old { self.sound_queue.push(SoundEvent::Damage); }
         }
         Ok(())
     }
 
-    /// Damages and d
-    fn spear(&mut self, targets: Vec<Vector>)   -> Result<()>  {
-
-        Ok(())
+    /// Returns the condition of every cell so changes can be heard afterwards
+    fn cond_snapshot(&self) -> Result<Vec<TerrainStatus>> {
+        let mut conds = vec![];
+        for cell in self.game.board().cells() { conds.push(*cell.get_cond()?); }
+        Ok(conds)
     }
-    fn cage(&mut self, _targets: Vec<Vector>)    -> Result<()>  { Ok(()) }
-
-    //Wraith Class
-    /// Damages all surrounding targets and heals user
-    /// Drains health from other players and shields (scaled to user level), damages ice
-    fn drain(&mut self, targets: Vec<Vector>) -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let lvl = self.player_ref[self.curr_player].get_level()? as f32;
-        let dmg_pow = pow * 10.0 + rng.gen_range(0.0, pow);
-        let shield_drain = lvl * 10.0 + rng.gen_range(0.0, lvl);
-        let mut hp_drain: f32 = 0.0;
-
-        //Damage everything hit
-        for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    hp_drain += damage;
-                    player.add_checked_hp(-damage)?;
-                }
-            }
-            //Check for TerrainStatus and decrement if hit
-            match cond {
-                TerrainStatus::Shielded => {
-                    hp_drain += shield_drain;
-                    self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter();
-                },
-                TerrainStatus::Frozen   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                _                       => {/*Ignore other types*/}
-            }
-        }
 
-        //Add total drained hp to user
-        self.player_ref[self.curr_player].add_checked_hp(hp_drain)?;
+    /// Queues one sound for each kind of terrain change since `before` was taken
+    fn queue_terrain_sounds(&mut self, before: &[TerrainStatus]) -> Result<()> {
+        let after = self.cond_snapshot()?;
+        let changed = |from: Option<TerrainStatus>, to: Option<TerrainStatus>| {
+            before.iter().zip(after.iter()).any(|(old, new)| {
+                old != new && from.is_none_or(|f| *old == f) && to.is_none_or(|t| *new == t)
+            })
+        };
 
+        if changed(None, Some(TerrainStatus::Burning))  { self.sound_queue.push(SoundEvent::Ignite); }
+        if changed(None, Some(TerrainStatus::Frozen))   { self.sound_queue.push(SoundEvent::Freeze); }
+        if changed(Some(TerrainStatus::Shielded), None) { self.sound_queue.push(SoundEvent::ShieldBreak); }
         Ok(())
     }
-    /// Freezes surrounding area, deals damage and teleports back to location this turn began at
-    /// Damages Shielded, stops Burning, and freezes normal
-    fn decoy(&mut self, targets: Vec<Vector>, origin: Vector) -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let dmg_pow = pow * 5.0 + rng.gen_range(0.0, pow);
 
-        for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    player.add_checked_hp(-damage)?;
-                }
-            }
-
-            match cond {
-                //Damage shields
-                TerrainStatus::Shielded => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                //Slightly strengthen frozen tiles
-                TerrainStatus::Frozen   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].inc_counter(); },
-                //Extinguish burning tiles without applying effect of burning
-                TerrainStatus::Burning  => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].reset_cond(); }
-                //Freeze normal tiles
-                TerrainStatus::Normal   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].cond_with_counter(TerrainStatus::Frozen, 3); },
-                _                       => {/*Ignore other types*/}
-            }
-        }
-        // Teleport back to starting loc
-        self.player_ref[self.curr_player].set_pos(origin)?;
+    /// True while a token is moving or an effect is playing
+    fn is_animating(&self) -> bool {
+        !self.token_motion.is_empty() || !self.effects.is_empty() || !self.ability_effects.is_empty()
+    }
 
+    /// Slides a player's token from one cell to another, replacing any movement already in progress
+    fn slide_token(&mut self, player: usize, from: Vector, to: Vector, duration: f64) -> Result<()> {
+        self.token_motion.retain(|(moving, _, _)| *moving != player);
+        let (id, callback) = self.completion();
+        self.token_motion.push((player, id, Tween::new(from, to, duration, Easing::EaseOut)?.with_callback(callback)));
         Ok(())
     }
-    /// Deals damage and inflicts the Crippled status ailment
-    /// Damages Shielded, and Frozen cells
-    fn rend(&mut self, targets: Vec<Vector>) -> Result<()>  {
-        let mut rng = rand::thread_rng();
-        let pow = *self.player_ref[self.curr_player].get_curr_stats()?.get_power();
-        let dmg_pow = pow * 10.0 + rng.gen_range(0.0, pow);
-
-        //Damage everything hit
+
+    /// Plays a short flash over every targeted cell
+    fn flash_targets(&mut self, targets: &[Vector]) -> Result<()> {
+        let keys = vec!["S1".to_string(),"S2".to_string(),"S3".to_string(),"S4".to_string(),"S5".to_string()];
         for target in targets {
-            let cell = &self.game_board.get_board()?[target.y as usize][target.x as usize];
-            let cond = *cell.get_cond()?;
-
-            //Check if it is a player and is not shielded
-            for player in &mut self.player_ref {
-                if player.get_pos()? == target && cond != TerrainStatus::Shielded { //Damage all unshielded players in range
-                    let damage = player.get_curr_stats()?.armor_reduce(dmg_pow);
-                    player.add_checked_hp(-damage)?;
-                    player.set_status(Status::Crippled, 3)?;
-                }
-            }
-            //Check for TerrainStatus and decrement if hit
-            match cond {
-                TerrainStatus::Shielded => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                TerrainStatus::Frozen   => { self.game_board.get_mut_board()?[target.y as usize][target.x as usize].decr_counter(); },
-                _                       => {/*Ignore other types*/}
-            }
+            let (id, callback) = self.completion();
+            self.effects.push((*target, id, Animator::uniform(keys.clone(), 0.06, Playback::Once)?.with_callback(callback)));
         }
         Ok(())
     }
-}
\ No newline at end of file
+
+}
@@ -2,6 +2,8 @@
 
 use crate::game_logic::scene_type::SceneReturn;
 use crate::game_logic::draw_helper::*;
+use crate::game_logic::sound_bank::{SoundBank, SoundEvent};
+use crate::game_logic::input_map::{InputMap, InputAction, PadSlot, Controls};
 
 //Resources
 use quicksilver::prelude::*;
@@ -16,24 +18,18 @@ pub struct ElderIntro {
     max_scenes: usize,
 
     text: Asset<Image>,
+    input: InputMap,
 }
 
 
 impl ElderIntro {
     /// Load the assets and initialise the game
     pub fn new() -> Result<Self> {
-        let font_mononoki = "square.ttf";
         let intro_background = "PHGameBackground.png";
         let overlay = "PHOverlayFade.png";
         let atlas_index = "Atlas_Intro_Index";
 
-        //Font Load
-        let text_info = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render(
-                "Welcome. [Enter] to progress",
-                &FontStyle::new(20.0, Color::BLACK),
-            )
-        }));
+        let input = InputMap::default();
 
         Ok(Self {
             intro_background: Asset::new(Image::load(intro_background)),
@@ -43,22 +39,50 @@ impl ElderIntro {
             curr_scene_index: 0,
             max_scenes: 4,
 
-            text: text_info,
+            text: ElderIntro::render_text(&input),
+            input,
         })
     }
 
+    /// Applies new key bindings, re-rendering the text that names them
+    pub fn set_input_map(&mut self, input: &InputMap) -> Result<()> {
+        if self.input == *input { return Ok(()); }
+        self.input = input.clone();
+        self.text = ElderIntro::render_text(&self.input);
+        Ok(())
+    }
+
+    /// Renders the label text for a set of bindings
+    fn render_text(input: &InputMap) -> Asset<Image> {
+        let font_mononoki = "square.ttf";
+        let text = format!("Welcome. [{}] to progress, [{}] settings",
+                           input.name(InputAction::Confirm), input.name(InputAction::Cancel));
+
+        //Font Load
+        Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(
+                &text,
+                &FontStyle::new(20.0, Color::BLACK),
+            )
+        }))
+    }
+
     /// Process keyboard and mouse, update the game state
-    pub fn update(&mut self, window: &mut Window) -> Result<SceneReturn> {
-        use ButtonState::*;
+    pub fn update(&mut self, window: &mut Window, sounds: &mut SoundBank) -> Result<SceneReturn> {
         let mut retval = SceneReturn::Good;
+        let controls = Controls::new(window, PadSlot::Any);
 
-        if window.keyboard()[Key::Return] == Pressed {
+        if self.input.pressed(&controls, InputAction::Confirm) {
+            sounds.play(SoundEvent::UiConfirm)?;
             if self.curr_scene_index < self.max_scenes - 1 {
                 self.curr_scene_index += 1;
             } else {
                 self.curr_scene_index = 0;
                 retval = SceneReturn::Finished;
             }
+        } else if self.input.pressed(&controls, InputAction::Cancel) {
+            sounds.play(SoundEvent::UiConfirm)?;
+            retval = SceneReturn::Settings;
         }
 
         Ok(retval)
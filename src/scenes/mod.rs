@@ -6,4 +6,6 @@ level.
 
 pub mod game;
 pub mod intro;
-pub mod outro;
\ No newline at end of file
+pub mod setup;
+pub mod outro;
+pub mod settings;
\ No newline at end of file
@@ -1,6 +1,8 @@
 use crate::game_logic::scene_type::SceneReturn;
 use crate::gameplay_logic::entities::PlayerType;
 use crate::game_logic::draw_helper::*;
+use crate::game_logic::sound_bank::{SoundBank, SoundEvent};
+use crate::game_logic::input_map::{InputMap, InputAction, PadSlot, Controls};
 
 //Resource
 use quicksilver::prelude::*;
@@ -16,6 +18,7 @@ pub struct ElderOutro {
     max_scenes: usize,
 
     text: Asset<Image>,
+    input: InputMap,
 
     winner: PlayerType,
 }
@@ -23,19 +26,12 @@ pub struct ElderOutro {
 impl ElderOutro {
     /// Load the assets and initialise the game
     pub fn new() -> Result<Self> {
-        let font_mononoki = "square.ttf";
         let background = "PHGameBackground.png";
         let overlay = "PHOverlayFade.png";
         //I declare like this because it is a sensible way to organize arbitrary ordered images
         let atlas_index = "Atlas_Outro_Index";
 
-        //Font Load
-        let text_info = Asset::new(Font::load(font_mononoki).and_then(|font| {
-            font.render(
-                "Game set match. [Enter] to progress",
-                &FontStyle::new(20.0, Color::BLACK),
-            )
-        }));
+        let input = InputMap::default();
 
         Ok(Self {
             outro_background: Asset::new(Image::load(background)),
@@ -45,18 +41,20 @@ impl ElderOutro {
             curr_scene_index: 0,
             max_scenes: 4,
 
-            text: text_info,
+            text: ElderOutro::render_text(&input),
+            input,
 
             winner: PlayerType::Undetermined,
         })
     }
 
     /// Process keyboard and mouse, update the game state
-    pub fn update(&mut self, window: &mut Window) -> Result<SceneReturn> {
-        use ButtonState::*;
+    pub fn update(&mut self, window: &mut Window, sounds: &mut SoundBank) -> Result<SceneReturn> {
         let mut retval = SceneReturn::Good;
+        let controls = Controls::new(window, PadSlot::Any);
 
-        if window.keyboard()[Key::Return] == Pressed {
+        if self.input.pressed(&controls, InputAction::Confirm) {
+            sounds.play(SoundEvent::UiConfirm)?;
             // Matches the winner and increments their scene counters.
             // Resetting and finishing when done
             if self.curr_scene_index < self.max_scenes - 1 { self.curr_scene_index += 1; } else {
@@ -103,10 +101,37 @@ impl ElderOutro {
         Ok(())
     }
 
+    /// Applies new key bindings, re-rendering the text that names them
+    pub fn set_input_map(&mut self, input: &InputMap) -> Result<()> {
+        if self.input == *input { return Ok(()); }
+        self.input = input.clone();
+        self.text = ElderOutro::render_text(&self.input);
+        Ok(())
+    }
+
+    /// Renders the label text for a set of bindings
+    fn render_text(input: &InputMap) -> Asset<Image> {
+        let font_mononoki = "square.ttf";
+        let text = format!("Game set match. [{}] to progress", input.name(InputAction::Confirm));
+
+        //Font Load
+        Asset::new(Font::load(font_mononoki).and_then(move |font| {
+            font.render(
+                &text,
+                &FontStyle::new(20.0, Color::BLACK),
+            )
+        }))
+    }
+
     /// Sets the winner of the game
     /// This can be called during game execution
     pub fn set_winner(&mut self, winner: PlayerType) -> Result<()> {
         self.winner = winner;
         Ok(())
     }
+
+    /// Gets the winner of the game
+    pub fn get_winner(&self) -> Result<PlayerType> {
+        Ok(self.winner)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,293 @@
+//This is the settings scene, reached from the intro or mid-game and returning to where it was opened
+
+use crate::game_logic::scene_type::SceneReturn;
+use crate::game_logic::draw_helper::*;
+use crate::game_logic::preferences::{Preferences, Palette, Opponent};
+use crate::game_logic::input_map::{InputMap, InputAction, INPUT_ACTIONS, PadSlot, Controls, pressed_key};
+use crate::game_logic::sound_bank::{SoundBank, SoundEvent};
+
+//Resources
+use quicksilver::prelude::*;
+
+//Choices for each row, values are pre-rendered in this order
+const VOLUME_STEPS: usize = 10;
+const SPEEDS: [f32; 5] = [0.5, 0.75, 1.0, 1.5, 2.0];
+const PALETTES: [Palette; 3] = [Palette::Standard, Palette::Deuteranopia, Palette::Tritanopia];
+const OPPONENTS: [Opponent; 2] = [Opponent::ElderGod, Opponent::Hunters];
+const PADS: [PadSlot; 6] = [PadSlot::Any, PadSlot::Slot(0), PadSlot::Slot(1), PadSlot::Slot(2), PadSlot::Slot(3), PadSlot::Off];
+
+/// The rows of the settings menu, top to bottom
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Row {
+    MusicVolume,
+    MusicMute,
+    SfxVolume,
+    AnimationSpeed,
+    Palette,
+    DefaultOpponent,
+    ElderGodPad,
+    HuntersPad,
+    Binding(InputAction),
+}
+
+pub struct ElderSettings {
+    settings_background: Asset<Image>,
+    settings_overlay: Asset<Image>,
+    underline: Asset<Image>,
+
+    title: Asset<Image>,
+    help: Asset<Image>,
+    waiting: Asset<Image>,
+    //One label per row and every value a row can take, binding rows have a single value re-rendered on change
+    rows: Vec<Row>,
+    labels: Vec<Asset<Image>>,
+    values: Vec<Vec<Asset<Image>>>,
+
+    preferences: Preferences,
+    input: InputMap,
+    curr_row: usize,
+    rebinding: bool, //Waiting for a key to bind to the selected row
+    save_error: Option<Asset<Image>>, //Shown after a failed save, leaving again without changes closes settings anyway
+}
+
+/// Renders settings text at a size
+fn render(text: String, size: f32) -> Asset<Image> {
+    let font_mononoki = "square.ttf";
+    Asset::new(Font::load(font_mononoki).and_then(move |font| {
+        font.render(&text, &FontStyle::new(size, Color::BLACK), )}))
+}
+
+impl ElderSettings {
+    /// Load the assets, the saved preferences and the saved key bindings
+    pub fn new() -> Result<Self> {
+        let background = "PHGameBackground.png";
+        let overlay = "PHOverlayFade.png";
+        let underline = "line.png";
+        let input = InputMap::load();
+
+        let mut rows = vec![Row::MusicVolume, Row::MusicMute, Row::SfxVolume, Row::AnimationSpeed, Row::Palette, Row::DefaultOpponent,
+                            Row::ElderGodPad, Row::HuntersPad];
+        rows.extend(INPUT_ACTIONS.iter().map(|action| Row::Binding(*action)));
+
+        let labels = rows.iter().map(|row| match row {
+            Row::MusicVolume      => render("Music Volume".to_string(), 18.0),
+            Row::MusicMute        => render("Music Mute".to_string(), 18.0),
+            Row::SfxVolume        => render("Effects Volume".to_string(), 18.0),
+            Row::AnimationSpeed   => render("Animation Speed".to_string(), 18.0),
+            Row::Palette          => render("Palette".to_string(), 18.0),
+            Row::DefaultOpponent  => render("Default Opponent".to_string(), 18.0),
+            Row::ElderGodPad      => render("Elder God Gamepad".to_string(), 18.0),
+            Row::HuntersPad       => render("Hunters Gamepad".to_string(), 18.0),
+            Row::Binding(action)  => render(format!("{} Key", action.name()), 18.0),
+        }).collect();
+
+        let volumes = || (0..=VOLUME_STEPS).map(|step| render(format!("< {}% >", step * 10), 18.0)).collect();
+        let pads = || PADS.iter().map(|pad| match pad {
+            PadSlot::Any        => render("< Any >".to_string(), 18.0),
+            PadSlot::Slot(slot) => render(format!("< Pad {} >", slot + 1), 18.0),
+            PadSlot::Off        => render("< None >".to_string(), 18.0),
+        }).collect();
+        let values = rows.iter().map(|row| match row {
+            Row::MusicVolume      => volumes(),
+            Row::MusicMute        => ["Off", "On"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::SfxVolume        => volumes(),
+            Row::AnimationSpeed   => SPEEDS.iter().map(|speed| render(format!("< {}x >", speed), 18.0)).collect(),
+            Row::Palette          => ["Standard", "Deuteranopia", "Tritanopia"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::DefaultOpponent  => ["Elder God", "Hunters"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::ElderGodPad      => pads(),
+            Row::HuntersPad       => pads(),
+            Row::Binding(action)  => vec![render(format!("[{}]", input.name(*action)), 18.0)],
+        }).collect();
+
+        Ok(Self {
+            settings_background: Asset::new(Image::load(background)),
+            settings_overlay: Asset::new(Image::load(overlay)),
+            underline: Asset::new(Image::load(underline)),
+
+            title: render("Settings".to_string(), 30.0),
+            help: render("Up/Down-Choose Left/Right-Change Enter-Rebind Esc-Save".to_string(), 20.0),
+            waiting: render("Press a key...".to_string(), 18.0),
+            rows,
+            labels,
+            values,
+
+            preferences: Preferences::load(),
+            input,
+            curr_row: 0,
+            rebinding: false,
+            save_error: None,
+        })
+    }
+
+    /// Process keyboard and mouse, update the game state
+    /// Settings always answer to the arrow keys, Enter and Escape so a bad binding can be undone here,
+    /// any gamepad can also choose and change rows with its D-pad and leave with Start
+    /// Leaving saves the preferences and bindings. A failed save is shown and keeps settings open, leaving
+    /// again without changing anything closes them anyway and the unsaved settings still apply this session
+    pub fn update(&mut self, window: &mut Window, sounds: &mut SoundBank) -> Result<SceneReturn> {
+        use ButtonState::*;
+        let mut retval = SceneReturn::Good;
+        let kb = window.keyboard();
+        let pads = Controls::new(window, PadSlot::Any);
+
+        if self.rebinding {
+            if let Some(key) = pressed_key(kb) {
+                if let Row::Binding(action) = self.rows[self.curr_row] {
+                    self.input.bind(action, key);
+                    self.render_bindings();
+                    self.save_error = None;
+                }
+                sounds.play(SoundEvent::UiConfirm)?;
+                self.rebinding = false;
+            }
+            return Ok(retval);
+        }
+
+        let up = kb[Key::Up] == Pressed || pads.button_pressed(GamepadButton::DpadUp);
+        let down = kb[Key::Down] == Pressed || pads.button_pressed(GamepadButton::DpadDown);
+        let left = kb[Key::Left] == Pressed || pads.button_pressed(GamepadButton::DpadLeft);
+        let right = kb[Key::Right] == Pressed || pads.button_pressed(GamepadButton::DpadRight);
+
+        if up && self.curr_row > 0 { self.curr_row -= 1; }
+        else if down && self.curr_row < self.rows.len() - 1 { self.curr_row += 1; }
+
+        if left { self.change(-1); sounds.play(SoundEvent::UiConfirm)?; }
+        else if right { self.change(1); sounds.play(SoundEvent::UiConfirm)?; }
+        if left || right { self.save_error = None; } //Changed settings are saved again on leaving
+
+        if kb[Key::Return] == Pressed {
+            if let Row::Binding(_) = self.rows[self.curr_row] {
+                sounds.play(SoundEvent::UiConfirm)?;
+                self.rebinding = true;
+            } else {
+                sounds.play(SoundEvent::UiDeny)?;
+            }
+        } else if kb[Key::Escape] == Pressed || pads.button_pressed(GamepadButton::Start) {
+            let failure = if self.save_error.is_some() { None } else { self.save() };
+            match failure {
+                Some(message) => {
+                    sounds.play(SoundEvent::UiDeny)?;
+                    self.save_error = Some(render(message, 16.0));
+                },
+                None => {
+                    sounds.play(SoundEvent::UiConfirm)?;
+                    self.save_error = None;
+                    self.curr_row = 0;
+                    retval = SceneReturn::Finished;
+                },
+            }
+        }
+
+        Ok(retval)
+    }
+
+    /// Draw stuff on the screen
+    pub fn draw(&mut self, window: &mut Window) -> Result<()> {
+        let window_center = Vector::new(window.screen_size().x as i32 / 2, window.screen_size().y as i32 / 2);
+
+        // Draw the background and overlay
+        draw_ex_with_center(window, &mut self.settings_background, window_center, Transform::IDENTITY, 1.0)?;
+        draw_ex_with_center(window, &mut self.settings_overlay, window_center, Transform::IDENTITY, 2.0)?;
+        draw_ex_with_center(window, &mut self.title, Vector::new(window_center.x, window_center.y - 270.0), Transform::IDENTITY, 4.0)?;
+
+        // Draw every row with its current value, underlining the selected one
+        for index in 0..self.rows.len() {
+            let y = window_center.y - 235.0 + 29.0 * index as f32;
+            let value = self.value_index(self.rows[index]);
+            draw_ex_with_center(window, &mut self.labels[index], Vector::new(window_center.x - 140.0, y), Transform::IDENTITY, 4.0)?;
+            if index == self.curr_row {
+                draw_ex_with_center(window, &mut self.underline, Vector::new(window_center.x - 140.0, y + 11.0), Transform::IDENTITY, 4.1)?;
+                if self.rebinding {
+                    draw_ex_with_center(window, &mut self.waiting, Vector::new(window_center.x + 140.0, y), Transform::IDENTITY, 4.0)?;
+                    continue;
+                }
+            }
+            draw_ex_with_center(window, &mut self.values[index][value], Vector::new(window_center.x + 140.0, y), Transform::IDENTITY, 4.0)?;
+        }
+
+        // Draw label text, should always render on top to show the state the game is in
+        //A failed save takes the place of the help line until settings are left or changed
+        let footer = self.save_error.as_mut().unwrap_or(&mut self.help);
+        draw_ex_with_center(window, footer, Vector::new(window_center.x, window_center.y + 286.0), Transform::IDENTITY, 4.0)?;
+
+        Ok(())
+    }
+
+    /// Handle various sorts of events, https://docs.rs/quicksilver/0.3.16/quicksilver/lifecycle/enum.Event.html
+    #[allow(unreachable_patterns, dead_code)]
+    pub fn event(&mut self, _event: &Event, _window: &mut Window) -> Result<()> {
+        //Do nothing
+        Ok(())
+    }
+
+    /// Returns the preferences as currently set
+    pub fn get_preferences(&self) -> Result<Preferences> {
+        Ok(self.preferences)
+    }
+
+    /// Returns the key bindings as currently set
+    pub fn get_input_map(&self) -> Result<InputMap> {
+        Ok(self.input.clone())
+    }
+
+    /// Saves the preferences and bindings, returns a message naming what could not be saved
+    fn save(&self) -> Option<String> {
+        let failures: Vec<String> = [
+            self.preferences.save().err().map(|error| format!("Preferences not saved: {}", error)),
+            self.input.save().err().map(|error| format!("Key bindings not saved: {}", error)),
+        ].iter().flatten().cloned().collect();
+
+        if failures.is_empty() { return None; }
+        Some(format!("{} - Esc to leave anyway", failures.join(", ")))
+    }
+
+    /// Re-renders the value of every binding row, binding one key can move another action's key
+    fn render_bindings(&mut self) {
+        for (index, row) in self.rows.iter().enumerate() {
+            if let Row::Binding(action) = row {
+                self.values[index] = vec![render(format!("[{}]", self.input.name(*action)), 18.0)];
+            }
+        }
+    }
+
+    /// Returns the index of the value a row is set to
+    fn value_index(&self, row: Row) -> usize {
+        let prefs = &self.preferences;
+        let volume_step = |volume: f32| ((volume * VOLUME_STEPS as f32).round() as usize).min(VOLUME_STEPS);
+        match row {
+            Row::MusicVolume     => volume_step(prefs.music_volume),
+            Row::MusicMute       => prefs.music_muted as usize,
+            Row::SfxVolume       => volume_step(prefs.sfx_volume),
+            //Saved speeds are always one of the choices, the closest one is used in case the file was edited
+            Row::AnimationSpeed  => (0..SPEEDS.len()).min_by(|a, b| {
+                    (SPEEDS[*a] - prefs.animation_speed).abs().partial_cmp(&(SPEEDS[*b] - prefs.animation_speed).abs())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }).unwrap_or(2),
+            Row::Palette         => PALETTES.iter().position(|palette| *palette == prefs.palette).unwrap_or(0),
+            Row::DefaultOpponent => OPPONENTS.iter().position(|opponent| *opponent == prefs.default_opponent).unwrap_or(0),
+            Row::ElderGodPad     => PADS.iter().position(|pad| *pad == prefs.elder_god_pad).unwrap_or(0),
+            Row::HuntersPad      => PADS.iter().position(|pad| *pad == prefs.hunters_pad).unwrap_or(0),
+            Row::Binding(_)      => 0,
+        }
+    }
+
+    /// Steps the selected row's value by `step` choices, stopping at either end
+    fn change(&mut self, step: i32) {
+        let row = self.rows[self.curr_row];
+        let count = self.values[self.curr_row].len() as i32;
+        let index = (self.value_index(row) as i32 + step).clamp(0, count - 1) as usize;
+
+        let prefs = &mut self.preferences;
+        match row {
+            Row::MusicVolume     => prefs.music_volume = index as f32 / VOLUME_STEPS as f32,
+            Row::MusicMute       => prefs.music_muted = index == 1,
+            Row::SfxVolume       => prefs.sfx_volume = index as f32 / VOLUME_STEPS as f32,
+            Row::AnimationSpeed  => prefs.animation_speed = SPEEDS[index],
+            Row::Palette         => prefs.palette = PALETTES[index],
+            Row::DefaultOpponent => prefs.default_opponent = OPPONENTS[index],
+            Row::ElderGodPad     => prefs.elder_god_pad = PADS[index],
+            Row::HuntersPad      => prefs.hunters_pad = PADS[index],
+            Row::Binding(_)      => {},
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//This is the setup scene, between the intro and the game, where the match is chosen
+
+use crate::game_logic::scene_type::SceneReturn;
+use crate::game_logic::draw_helper::*;
+use crate::game_logic::preferences::Preferences;
+use crate::game_logic::input_map::{InputMap, InputAction, PadSlot, Controls};
+use crate::game_logic::sound_bank::{SoundBank, SoundEvent};
+use crate::gameplay_logic::entities::PlayerType;
+use crate::gameplay_logic::game_levels::{Levels, LEVEL_COUNT};
+use crate::gameplay_logic::match_config::{MatchConfig, MONSTER_STAGES, MAX_HUNTER_LEVEL};
+use crate::gameplay_logic::turn_order::TURN_SCHEMES;
+
+//Resources
+use quicksilver::prelude::*;
+use rand::Rng;
+
+//Choices for each row, values are pre-rendered in this order
+const SIDES: [PlayerType; 2] = [PlayerType::Player2, PlayerType::Player1];
+const ROUND_LIMITS: [Option<u32>; 4] = [None, Some(10), Some(20), Some(30)];
+
+/// The rows of the setup menu, top to bottom
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Row {
+    Level,
+    PlayerOne,
+    HunterLevel,
+    MonsterStage,
+    TurnScheme,
+    LevelConditions,
+    RoundLimit,
+    FogOfWar,
+    Handoff,
+}
+
+const ROWS: [Row; 9] = [Row::Level, Row::PlayerOne, Row::HunterLevel, Row::MonsterStage, Row::TurnScheme,
+                        Row::LevelConditions, Row::RoundLimit, Row::FogOfWar, Row::Handoff];
+
+pub struct ElderSetup {
+    setup_background: Asset<Image>,
+    setup_overlay: Asset<Image>,
+    underline: Asset<Image>,
+
+    title: Asset<Image>,
+    help: Asset<Image>,
+    //One label per row and every value a row can take
+    labels: Vec<Asset<Image>>,
+    values: Vec<Vec<Asset<Image>>>,
+
+    config: MatchConfig,
+    input: InputMap,
+    curr_row: usize,
+}
+
+/// Renders setup text at a size
+fn render(text: String, size: f32) -> Asset<Image> {
+    let font_mononoki = "square.ttf";
+    Asset::new(Font::load(font_mononoki).and_then(move |font| {
+        font.render(&text, &FontStyle::new(size, Color::BLACK), )}))
+}
+
+impl ElderSetup {
+    /// Load the assets and start from the default match
+    pub fn new() -> Result<Self> {
+        let background = "PHGameBackground.png";
+        let overlay = "PHOverlayFade.png";
+        let underline = "line.png";
+        let input = InputMap::default();
+        let levels = Levels::new()?;
+
+        let labels = ROWS.iter().map(|row| match row {
+            Row::Level           => render("Map".to_string(), 18.0),
+            Row::PlayerOne       => render("Player 1 Plays".to_string(), 18.0),
+            Row::HunterLevel     => render("Hunter Level".to_string(), 18.0),
+            Row::MonsterStage    => render("Monster Stage".to_string(), 18.0),
+            Row::TurnScheme      => render("Turn Order".to_string(), 18.0),
+            Row::LevelConditions => render("Map Conditions".to_string(), 18.0),
+            Row::RoundLimit      => render("Round Limit".to_string(), 18.0),
+            Row::FogOfWar        => render("Fog of War".to_string(), 18.0),
+            Row::Handoff         => render("Hotseat Handoff".to_string(), 18.0),
+        }).collect();
+
+        let values = ROWS.iter().map(|row| match row {
+            Row::Level           => (1..=LEVEL_COUNT).map(|level| {
+                    render(format!("< {} >", levels.get_name(level).expect("Every level has a name")), 18.0)
+                }).collect(),
+            Row::PlayerOne       => ["Hunters", "Elder God"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::HunterLevel     => (1..=MAX_HUNTER_LEVEL).map(|level| render(format!("< {} >", level), 18.0)).collect(),
+            Row::MonsterStage    => MONSTER_STAGES.iter().map(|stage| render(format!("< {} >", stage.key()), 18.0)).collect(),
+            Row::TurnScheme      => TURN_SCHEMES.iter().map(|scheme| render(format!("< {} >", scheme.name()), 18.0)).collect(),
+            Row::LevelConditions => ["On", "Off"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::RoundLimit      => ROUND_LIMITS.iter().map(|limit| match limit {
+                    Some(rounds) => render(format!("< {} >", rounds), 18.0),
+                    None         => render("< None >".to_string(), 18.0),
+                }).collect(),
+            Row::FogOfWar        => ["Off", "On"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+            Row::Handoff         => ["Off", "On"].iter().map(|name| render(format!("< {} >", name), 18.0)).collect(),
+        }).collect();
+
+        Ok(Self {
+            setup_background: Asset::new(Image::load(background)),
+            setup_overlay: Asset::new(Image::load(overlay)),
+            underline: Asset::new(Image::load(underline)),
+
+            title: render("Match Setup".to_string(), 30.0),
+            help: ElderSetup::render_help(&input),
+            labels,
+            values,
+
+            config: MatchConfig::default(),
+            input,
+            curr_row: 0,
+        })
+    }
+
+    /// Applies the player's preferences, the first human faces their default opponent
+    pub fn set_preferences(&mut self, preferences: &Preferences) -> Result<()> {
+        self.config.set_opponent(preferences.default_opponent);
+        Ok(())
+    }
+
+    /// Applies new key bindings, re-rendering the text that names them
+    pub fn set_input_map(&mut self, input: &InputMap) -> Result<()> {
+        if self.input == *input { return Ok(()); }
+        self.input = input.clone();
+        self.help = ElderSetup::render_help(&self.input);
+        Ok(())
+    }
+
+    /// Renders the help text for a set of bindings
+    fn render_help(input: &InputMap) -> Asset<Image> {
+        render(format!("{}/{}-Choose {}/{}-Change {}-Start {}-Back",
+                       input.name(InputAction::Up), input.name(InputAction::Down),
+                       input.name(InputAction::Left), input.name(InputAction::Right),
+                       input.name(InputAction::Confirm), input.name(InputAction::Cancel)), 20.0)
+    }
+
+    /// Process keyboard and mouse, update the game state
+    /// Confirming finishes the scene so the match starts with `get_config`, cancelling goes back to the title
+    pub fn update(&mut self, window: &mut Window, sounds: &mut SoundBank) -> Result<SceneReturn> {
+        let mut retval = SceneReturn::Good;
+        let controls = Controls::new(window, PadSlot::Any);
+
+        if self.input.pressed(&controls, InputAction::Up) && self.curr_row > 0 { self.curr_row -= 1; }
+        else if self.input.pressed(&controls, InputAction::Down) && self.curr_row < ROWS.len() - 1 { self.curr_row += 1; }
+
+        if self.input.pressed(&controls, InputAction::Left) { self.change(-1); sounds.play(SoundEvent::UiConfirm)?; }
+        else if self.input.pressed(&controls, InputAction::Right) { self.change(1); sounds.play(SoundEvent::UiConfirm)?; }
+
+        if self.input.pressed(&controls, InputAction::Confirm) {
+            sounds.play(SoundEvent::UiConfirm)?;
+            self.config.seed = rand::thread_rng().gen(); //Every new match gets its own environment
+            self.curr_row = 0;
+            retval = SceneReturn::Finished;
+        } else if self.input.pressed(&controls, InputAction::Cancel) {
+            sounds.play(SoundEvent::UiDeny)?;
+            self.curr_row = 0;
+            retval = SceneReturn::Title;
+        }
+
+        Ok(retval)
+    }
+
+    /// Draw stuff on the screen
+    pub fn draw(&mut self, window: &mut Window) -> Result<()> {
+        let window_center = Vector::new(window.screen_size().x as i32 / 2, window.screen_size().y as i32 / 2);
+
+        // Draw the background and overlay
+        draw_ex_with_center(window, &mut self.setup_background, window_center, Transform::IDENTITY, 1.0)?;
+        draw_ex_with_center(window, &mut self.setup_overlay, window_center, Transform::IDENTITY, 2.0)?;
+        draw_ex_with_center(window, &mut self.title, Vector::new(window_center.x, window_center.y - 200.0), Transform::IDENTITY, 4.0)?;
+
+        // Draw every row with its current value, underlining the selected one
+        for (index, row) in ROWS.iter().enumerate() {
+            let y = window_center.y - 150.0 + 40.0 * index as f32;
+            let value = self.value_index(*row);
+            draw_ex_with_center(window, &mut self.labels[index], Vector::new(window_center.x - 140.0, y), Transform::IDENTITY, 4.0)?;
+            draw_ex_with_center(window, &mut self.values[index][value], Vector::new(window_center.x + 140.0, y), Transform::IDENTITY, 4.0)?;
+            if index == self.curr_row {
+                draw_ex_with_center(window, &mut self.underline, Vector::new(window_center.x - 140.0, y + 11.0), Transform::IDENTITY, 4.1)?;
+            }
+        }
+
+        // Draw label text, should always render on top to show the state the game is in
+        draw_ex_with_center(window, &mut self.help, Vector::new(window_center.x, window_center.y + 286.0), Transform::IDENTITY, 4.0)?;
+
+        Ok(())
+    }
+
+    /// Handle various sorts of events, https://docs.rs/quicksilver/0.3.16/quicksilver/lifecycle/enum.Event.html
+    #[allow(unreachable_patterns, dead_code)]
+    pub fn event(&mut self, _event: &Event, _window: &mut Window) -> Result<()> {
+        //Do nothing
+        Ok(())
+    }
+
+    /// Returns the match as currently set up
+    pub fn get_config(&self) -> Result<MatchConfig> {
+        Ok(self.config)
+    }
+
+    /// Returns the index of the value a row is set to
+    fn value_index(&self, row: Row) -> usize {
+        let config = &self.config;
+        match row {
+            Row::Level           => config.level - 1,
+            Row::PlayerOne       => SIDES.iter().position(|side| *side == config.player_one).unwrap_or(0),
+            Row::HunterLevel     => config.hunter_level as usize - 1,
+            Row::MonsterStage    => MONSTER_STAGES.iter().position(|stage| *stage == config.monster_stage).unwrap_or(0),
+            Row::TurnScheme      => TURN_SCHEMES.iter().position(|scheme| *scheme == config.turn_scheme).unwrap_or(0),
+            Row::LevelConditions => if config.level_conditions { 0 } else { 1 },
+            Row::RoundLimit      => ROUND_LIMITS.iter().position(|limit| *limit == config.round_limit).unwrap_or(0),
+            Row::FogOfWar        => if config.fog_of_war { 1 } else { 0 },
+            Row::Handoff         => if config.handoff { 1 } else { 0 },
+        }
+    }
+
+    /// Steps the selected row's value by `step` choices, stopping at either end
+    fn change(&mut self, step: i32) {
+        let row = ROWS[self.curr_row];
+        let count = self.values[self.curr_row].len() as i32;
+        let index = (self.value_index(row) as i32 + step).clamp(0, count - 1) as usize;
+
+        let config = &mut self.config;
+        match row {
+            Row::Level           => config.level = index + 1,
+            Row::PlayerOne       => config.player_one = SIDES[index],
+            Row::HunterLevel     => config.hunter_level = index as u32 + 1,
+            Row::MonsterStage    => config.monster_stage = MONSTER_STAGES[index],
+            Row::TurnScheme      => config.turn_scheme = TURN_SCHEMES[index],
+            Row::LevelConditions => config.level_conditions = index == 0,
+            Row::RoundLimit      => config.round_limit = ROUND_LIMITS[index],
+            Row::FogOfWar        => config.fog_of_war = index == 1,
+            Row::Handoff         => config.handoff = index == 1,
+        }
+    }
+}
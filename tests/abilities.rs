@@ -0,0 +1,300 @@
+/*
+Rules tests for what every ability does to the entities and land it reaches. Damage has a random part, so it is
+checked against the range the rules allow: power times the ability's scale plus up to one more power, less armor.
+*/
+
+mod common;
+
+use common::scenario::{affected, assert_between, at, cell, hp, Scenario};
+
+use i_am_the_elder_god::gameplay_logic::entities::{ClassType, Status, StatusEffect};
+use i_am_the_elder_god::gameplay_logic::game_match::{Command, MatchEvent};
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Direction, Terrain, TerrainStatus};
+use i_am_the_elder_god::gameplay_logic::turn_order::TurnScheme;
+
+//Support
+
+#[test]
+fn bio_heals_allies_and_hurts_enemies_in_range() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 7)
+        .entity(ClassType::Support, 1, 5, 5)
+        .entity(ClassType::Assault, 1, 6, 5).hp(50.0)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Bio"));
+
+    //Power 2 heals 20 to 22, the Wraith's armor of 2 takes a fifth off
+    let assault = game.entities()[2];
+    assert_between(hp(&assault), 70.0, 72.0);
+    assert!(assault.has_effect(StatusEffect::Regenerating));
+    let wraith = game.entities()[0];
+    assert_between(hp(&wraith), 200.0 - 17.6, 200.0 - 16.0);
+    assert!(!wraith.has_effect(StatusEffect::Regenerating));
+}
+
+#[test]
+fn shield_covers_the_cell_of_every_ally() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 10, 10)
+        .entity(ClassType::Support, 2, 5, 5)
+        .entity(ClassType::Assault, 1, 8, 8)
+        .entity(ClassType::Trapper, 1, 2, 2)
+        .start();
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Shield"));
+
+    for (x, y) in [(5, 5), (8, 8), (2, 2)].iter() {
+        let shielded = cell(game.board(), *x, *y);
+        assert_eq!(*shielded.get_cond().expect("Cond"), TerrainStatus::Shielded);
+        assert_eq!(shielded.get_counter().expect("Counter"), 2, "Shields are as strong as the Support's power");
+    }
+    assert_eq!(*cell(game.board(), 10, 10).get_cond().expect("Cond"), TerrainStatus::Normal);
+}
+
+#[test]
+fn renew_raises_the_downed_and_cleanses_them() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 10, 10)
+        .entity(ClassType::Support, 3, 5, 5)
+        .entity(ClassType::Assault, 1, 5, 6).hp(0.0).status(Status::Downed, 3)
+            .effect(StatusEffect::Marked, 2).effect(StatusEffect::Regenerating, 2)
+        .entity(ClassType::Trapper, 1, 5, 4).hp(0.0).status(Status::Dead, 0)
+        .start();
+    assert!(game.apply(Command::Act(3, Direction::Up)).expect("Renew"));
+
+    //Power 2 would restore 4% of max hp, which is raised to the lowest amount of 10%
+    let assault = game.entities()[2];
+    assert_eq!(assault.get_status().expect("Status"), Status::Normal);
+    assert_between(hp(&assault), 9.0, 9.0);
+    assert_eq!(assault.get_effects(), vec![StatusEffect::Regenerating]);
+
+    let trapper = game.entities()[3];
+    assert_eq!(trapper.get_status().expect("Status"), Status::Dead, "The dead cannot be revived");
+    assert_eq!(hp(&trapper), 0.0);
+}
+
+//Assault
+
+#[test]
+fn pierce_damages_and_marks_and_chips_ice() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 6)
+        .entity(ClassType::Assault, 1, 5, 8)
+        .condition(5, 7, TerrainStatus::Frozen, 2)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Pierce"));
+
+    let wraith = game.entities()[0];
+    assert_between(hp(&wraith), 200.0 - 35.2, 200.0 - 32.0);
+    assert!(wraith.has_effect(StatusEffect::Marked));
+    let ice = cell(game.board(), 5, 7);
+    assert_eq!((*ice.get_cond().expect("Cond"), ice.get_counter().expect("Counter")), (TerrainStatus::Frozen, 1));
+}
+
+#[test]
+fn bleeding_weakens_the_hunter_it_is_on() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 6)
+        .entity(ClassType::Assault, 1, 5, 8).effect(StatusEffect::Bleeding, 2)
+        .start();
+    let mut assault = game.entities()[1];
+    assert_eq!(*assault.get_curr_stats().expect("Stats").get_power(), 3.0, "A quarter off power 4");
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Pierce"));
+
+    //Power 3 hits for 30 to 33 before armor
+    assert_between(hp(&game.entities()[0]), 200.0 - 26.4, 200.0 - 24.0);
+}
+
+#[test]
+fn pierce_cannot_hurt_what_a_shield_covers() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 7).condition(5, 7, TerrainStatus::Shielded, 2)
+        .entity(ClassType::Assault, 1, 5, 8)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Pierce"));
+
+    let wraith = game.entities()[0];
+    assert_eq!(hp(&wraith), 200.0);
+    assert!(!wraith.has_effect(StatusEffect::Marked));
+    assert_eq!(cell(game.board(), 5, 7).get_counter().expect("Counter"), 1);
+}
+
+#[test]
+fn grenade_sets_fire_and_weakens_shields() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 4)
+        .entity(ClassType::Assault, 2, 5, 8)
+        .condition(6, 5, TerrainStatus::Shielded, 3)
+        .start();
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Grenade"));
+
+    for (x, y) in [(5, 4), (4, 5), (5, 5), (5, 6)].iter() {
+        let burning = cell(game.board(), *x, *y);
+        assert_eq!((*burning.get_cond().expect("Cond"), burning.get_counter().expect("Counter")), (TerrainStatus::Burning, 4));
+    }
+    assert_eq!(cell(game.board(), 6, 5).get_counter().expect("Counter"), 2);
+    assert_between(hp(&game.entities()[0]), 200.0 - 35.2, 200.0 - 32.0);
+}
+
+#[test]
+fn airraid_destroys_the_land_it_hits_but_not_cities() {
+    let mut game = Scenario::open_field().seed(3)
+        .entity(ClassType::Wraith, 1, 6, 9)
+        .entity(ClassType::Assault, 3, 5, 12)
+        .land(5, 9, Terrain::City)
+        .condition(4, 9, TerrainStatus::Shielded, 2)
+        .start();
+    assert!(game.apply(Command::Act(3, Direction::Up)).expect("Air raid"));
+    let hits = affected(&mut game);
+    assert!(!hits.is_empty());
+
+    for target in game.board().cells() {
+        let pos = target.get_pos().expect("Pos");
+        let in_area = (pos.x - 5.0).abs() + (pos.y - 9.0).abs() <= 3.0 && pos != at(5, 12) && pos != at(5, 9);
+        let expected = if hits.contains(&pos) { Terrain::Destroyed } else { Terrain::Plain };
+        assert!(in_area || !hits.contains(&pos), "Air raid hit {:?} outside its area", pos);
+        if pos != at(5, 9) { assert_eq!(*target.get_land().expect("Land"), expected, "Land at {:?}", pos); }
+    }
+    assert_eq!(*cell(game.board(), 5, 9).get_land().expect("Land"), Terrain::City);
+
+    let shield = if hits.contains(&at(4, 9)) { TerrainStatus::Normal } else { TerrainStatus::Shielded };
+    assert_eq!(*cell(game.board(), 4, 9).get_cond().expect("Cond"), shield);
+    if hits.contains(&at(6, 9)) {
+        assert_between(hp(&game.entities()[0]), 200.0 - 99.2, 200.0 - 96.0);
+    } else {
+        assert_eq!(hp(&game.entities()[0]), 200.0);
+    }
+}
+
+//Trapper
+
+#[test]
+fn caltrop_spikes_open_unshielded_ground() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 12, 12)
+        .entity(ClassType::Trapper, 1, 5, 5)
+        .land(6, 4, Terrain::Road)
+        .land(6, 5, Terrain::Destroyed)
+        .land(7, 5, Terrain::Forest)
+        .condition(6, 6, TerrainStatus::Shielded, 1)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Right)).expect("Caltrop"));
+
+    let lands: Vec<Terrain> = [(6, 4), (6, 5), (7, 5), (6, 6), (5, 5)].iter()
+        .map(|(x, y)| *cell(game.board(), *x, *y).get_land().expect("Land")).collect();
+    assert_eq!(lands, vec![Terrain::Spikes, Terrain::Spikes, Terrain::Forest, Terrain::Plain, Terrain::Plain]);
+}
+
+#[test]
+fn spear_stuns_the_enemy_it_reaches() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 6)
+        .entity(ClassType::Trapper, 2, 5, 10)
+        .start();
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Spear"));
+
+    let mut wraith = game.entities()[0];
+    assert!(wraith.has_effect(StatusEffect::Stunned));
+    assert_eq!(*wraith.get_curr_stats().expect("Stats").get_actions(), 0.0);
+}
+
+#[test]
+fn spear_stops_at_an_ally_without_stunning_it() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 6)
+        .entity(ClassType::Trapper, 2, 5, 10)
+        .entity(ClassType::Support, 1, 5, 8)
+        .start();
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Spear"));
+
+    assert!(game.entities().iter().all(|entity| !entity.has_effect(StatusEffect::Stunned)));
+}
+
+#[test]
+fn cage_roots_enemies_on_its_ring_only() {
+    let mut ring = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 8)
+        .entity(ClassType::Trapper, 3, 5, 5)
+        .start();
+    assert!(ring.apply(Command::Act(3, Direction::Up)).expect("Cage"));
+    let mut wraith = ring.entities()[0];
+    assert!(wraith.has_effect(StatusEffect::Rooted));
+    assert_eq!(*wraith.get_curr_stats().expect("Stats").get_speed(), 0.0);
+
+    let mut inside = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 7)
+        .entity(ClassType::Trapper, 3, 5, 5)
+        .start();
+    assert!(inside.apply(Command::Act(3, Direction::Up)).expect("Cage"));
+    assert!(!inside.entities()[0].has_effect(StatusEffect::Rooted));
+
+    let mut shielded = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 8).condition(5, 8, TerrainStatus::Shielded, 1)
+        .entity(ClassType::Trapper, 3, 5, 5)
+        .start();
+    assert!(shielded.apply(Command::Act(3, Direction::Up)).expect("Cage"));
+    assert!(!shielded.entities()[0].has_effect(StatusEffect::Rooted));
+}
+
+//Wraith, which every monster stage fights with
+
+#[test]
+fn drain_heals_the_monster_by_what_it_takes() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::Initiative)
+        .entity(ClassType::Wraith, 1, 5, 5).hp(100.0)
+        .entity(ClassType::Support, 1, 5, 4).stats(100.0, 3.0, 0.0, 2.0, 1.0)
+        .condition(4, 5, TerrainStatus::Shielded, 2)
+        .condition(6, 5, TerrainStatus::Frozen, 2)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Drain"));
+
+    //Without armor the Support takes all of the 20 to 22 the Wraith's power deals
+    let taken = 100.0 - hp(&game.entities()[1]);
+    assert_between(taken, 20.0, 22.0);
+    //A level 1 monster drains 10 to 11 more from each shield it touches
+    assert_between(hp(&game.entities()[0]) - 100.0 - taken, 10.0, 11.0);
+    assert_eq!(cell(game.board(), 4, 5).get_counter().expect("Counter"), 1);
+    assert_eq!(cell(game.board(), 6, 5).get_counter().expect("Counter"), 1);
+}
+
+#[test]
+fn decoy_freezes_around_the_monster_and_sends_it_back_unseen() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::Initiative)
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Support, 1, 7, 5).condition(7, 5, TerrainStatus::Frozen, 2)
+        .condition(6, 4, TerrainStatus::Burning, 2)
+        .condition(6, 6, TerrainStatus::Shielded, 2)
+        .start();
+    assert!(game.apply(Command::Move(Direction::Right)).expect("Move"));
+    game.drain_events();
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Decoy"));
+
+    let conditions: Vec<(TerrainStatus, u32)> = [(6, 4), (5, 5), (7, 5), (6, 6)].iter().map(|(x, y)| {
+        let target = cell(game.board(), *x, *y);
+        (*target.get_cond().expect("Cond"), target.get_counter().expect("Counter"))
+    }).collect();
+    assert_eq!(conditions, vec![(TerrainStatus::Normal, 0), (TerrainStatus::Frozen, 3),
+                                (TerrainStatus::Frozen, 3), (TerrainStatus::Shielded, 1)]);
+    assert_eq!(*cell(game.board(), 6, 4).get_land().expect("Land"), Terrain::Plain, "Put out fire leaves the land");
+
+    let wraith = game.entities()[0];
+    assert_eq!(wraith.get_pos().expect("Pos"), at(5, 5));
+    assert!(wraith.has_effect(StatusEffect::Invisible));
+    assert!(game.drain_events().contains(&MatchEvent::Teleported { entity: 0, from: at(6, 5), to: at(5, 5) }));
+    assert_between(hp(&game.entities()[1]), 100.0 - 9.6, 100.0 - 8.0);
+}
+
+#[test]
+fn rend_cripples_and_bleeds_what_it_tears() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::Initiative)
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Assault, 1, 5, 3)
+        .condition(4, 4, TerrainStatus::Frozen, 2)
+        .start();
+    assert!(game.apply(Command::Act(3, Direction::Up)).expect("Rend"));
+
+    let mut assault = game.entities()[1];
+    assert_between(hp(&assault), 90.0 - 19.8, 90.0 - 18.0);
+    assert!(assault.has_effect(StatusEffect::Crippled) && assault.has_effect(StatusEffect::Bleeding));
+    assert_eq!(*assault.get_curr_stats().expect("Stats").get_speed(), 2.0, "Crippled halves speed");
+    assert_eq!(cell(game.board(), 4, 4).get_counter().expect("Counter"), 1);
+}
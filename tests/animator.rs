@@ -0,0 +1,83 @@
+/*
+Tests for time based sprite sequences and tweens.
+*/
+
+use i_am_the_elder_god::gameplay_logic::animator::{Animator, Callback, Clock, Easing, Playback, Tween};
+
+use quicksilver::geom::Vector;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Returns a callback that counts how often it runs, and the count
+fn counter() -> (Callback, Rc<Cell<u32>>) {
+    let count = Rc::new(Cell::new(0));
+    let counted = Rc::clone(&count);
+    (Box::new(move || counted.set(counted.get() + 1)), count)
+}
+
+#[test]
+fn an_animator_needs_frames() {
+    assert!(Animator::new(vec![], Playback::Once).is_err());
+    assert!(Animator::uniform(vec![], 0.1, Playback::Loop).is_err());
+}
+
+#[test]
+fn frames_advance_by_elapsed_time() {
+    let frames = vec![("a".to_string(), 0.25), ("b".to_string(), 0.5)];
+    let mut once = Animator::new(frames.clone(), Playback::Once).expect("Animator");
+    once.update(0.375);
+    assert_eq!(once.current_key(), "b");
+    once.update(0.375);
+    assert!(once.is_finished());
+    assert_eq!(once.current_key(), "b");
+
+    let mut looping = Animator::new(frames, Playback::Loop).expect("Animator");
+    looping.update(0.875); //One whole cycle and part of the first frame
+    assert_eq!(looping.current_key(), "a");
+    assert!(!looping.is_finished());
+}
+
+#[test]
+fn tweens_end_on_their_target() {
+    let mut tween = Tween::new(Vector::new(0.0, 0.0), Vector::new(2.0, 4.0), 0.5, Easing::EaseOut).expect("Tween");
+    tween.update(0.25);
+    assert!(!tween.is_finished());
+    tween.update(1.0);
+    assert!(tween.is_finished());
+    assert_eq!(tween.value(), Vector::new(2.0, 4.0));
+}
+
+#[test]
+fn callbacks_run_when_animations_complete() {
+    let frames = vec![("a".to_string(), 0.25), ("b".to_string(), 0.25)];
+    let (callback, once_count) = counter();
+    let mut once = Animator::new(frames.clone(), Playback::Once).expect("Animator").with_callback(callback);
+    once.update(0.25);
+    assert_eq!(once_count.get(), 0);
+    once.update(1.0);
+    once.update(1.0);
+    assert_eq!(once_count.get(), 1);
+
+    //Looping animations complete at the end of every cycle
+    let (callback, loop_count) = counter();
+    let mut looping = Animator::new(frames, Playback::Loop).expect("Animator").with_callback(callback);
+    looping.update(1.25);
+    assert_eq!(loop_count.get(), 2);
+
+    let (callback, tween_count) = counter();
+    let mut tween = Tween::new(Vector::new(0.0, 0.0), Vector::new(1.0, 0.0), 0.5, Easing::Linear).expect("Tween").with_callback(callback);
+    tween.update(0.25);
+    assert_eq!(tween_count.get(), 0);
+    tween.update(0.5);
+    tween.update(0.5);
+    assert_eq!(tween_count.get(), 1);
+}
+
+#[test]
+fn clocks_start_at_zero_and_never_jump_far() {
+    let mut clock = Clock::new();
+    assert_eq!(clock.tick(), 0.0);
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    let delta = clock.tick();
+    assert!((0.02..=0.25).contains(&delta), "{}", delta);
+}
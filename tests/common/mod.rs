@@ -0,0 +1,39 @@
+/*
+Helpers shared by the integration tests. Golden files hold the text a test is expected to produce and live in
+`tests/golden`. Run the tests with `UPDATE_GOLDEN=1` to write the current output over them, then review the
+change to the golden file like any other diff.
+*/
+
+#![allow(dead_code)] //Each test file uses a different part of these helpers
+
+/// Builds matches by hand for rules tests
+pub mod scenario;
+
+use i_am_the_elder_god::gameplay_logic::snapshot::Snapshot;
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Returns where a golden file is kept
+pub fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{}.txt", name))
+}
+
+/// Checks `actual` against the golden file `name`, or writes it there when `UPDATE_GOLDEN` is set
+/// Snapshot lines that differ are reported field by field
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().expect("Golden files are in a folder")).expect("Cannot create the golden folder");
+        fs::write(&path, actual).expect("Cannot write the golden file");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("No golden file at {}, run with UPDATE_GOLDEN=1 to create it", path.display()));
+    if expected.replace("\r\n", "\n") != actual {
+        let changes = Snapshot::parse(&expected).diff_text(&Snapshot::parse(actual));
+        panic!("Output differs from {}\n--- changes from the golden file ---\n{}--- output ---\n{}",
+               path.display(), changes, actual);
+    }
+}
@@ -0,0 +1,164 @@
+/*
+Builds matches by hand for rules tests. A scenario starts as an open field of normal plain the size of a level,
+then land, conditions and entities are placed on it cell by cell so each test shows exactly what it depends on.
+Entity settings such as `hp` and `effect` apply to the entity placed last.
+*/
+
+use i_am_the_elder_god::gameplay_logic::coord::Coord;
+use i_am_the_elder_god::gameplay_logic::entities::{Entity, ClassType, PlayerType, Status, StatusEffect};
+use i_am_the_elder_god::gameplay_logic::game_board::{Cell, GameBoard};
+use i_am_the_elder_god::gameplay_logic::game_match::{Match, MatchEvent};
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Terrain, TerrainStatus};
+use i_am_the_elder_god::gameplay_logic::match_config::MatchConfig;
+use i_am_the_elder_god::gameplay_logic::turn_order::TurnScheme;
+
+use quicksilver::geom::Vector;
+
+/// Returns the position of a cell
+pub fn at(x: usize, y: usize) -> Vector {
+    Vector::new(x as f32, y as f32)
+}
+
+/// Returns positions as whole cells, which read better in assertions than vectors
+pub fn coords(positions: &[Vector]) -> Vec<(usize, usize)> {
+    positions.iter().map(|pos| (pos.x as usize, pos.y as usize)).collect()
+}
+
+/// Returns positions as whole cells in board order, for targets whose order does not matter
+pub fn sorted_coords(positions: &[Vector]) -> Vec<(usize, usize)> {
+    let mut cells = coords(positions);
+    cells.sort_by_key(|(x, y)| (*y, *x));
+    cells
+}
+
+/// Returns the cell at a position
+pub fn cell(board: &GameBoard, x: usize, y: usize) -> Cell {
+    *board.cell(Coord::new(x as i32, y as i32)).expect("On the board")
+}
+
+/// Returns the current hp of an entity
+pub fn hp(entity: &Entity) -> f32 {
+    let mut entity = *entity;
+    *entity.get_curr_stats().expect("Stats").get_hp()
+}
+
+/// Checks that a value is within `low` and `high`, inclusive, allowing for rounding
+pub fn assert_between(value: f32, low: f32, high: f32) {
+    assert!(value >= low - 0.001 && value <= high + 0.001, "{} is not between {} and {}", value, low, high);
+}
+
+/// Returns the cells the last ability reported it affected
+pub fn affected(game: &mut Match) -> Vec<Vector> {
+    game.drain_events().into_iter().filter_map(|event| match event {
+        MatchEvent::Ability { affected, .. } => Some(affected),
+        _                                    => None,
+    }).next_back().expect("No ability was used")
+}
+
+/// A board and entities placed by hand, and the rules to start a match on them with
+pub struct Scenario {
+    config: MatchConfig,
+    board: GameBoard,
+    entities: Vec<Entity>,
+}
+
+impl Scenario {
+    /// Starts an open field of normal plain with nobody on it
+    pub fn open_field() -> Self {
+        let config = MatchConfig { level_conditions: false, ..MatchConfig::default() };
+        let mut board = GameBoard::new(config.level).expect("Level 1 exists");
+        for cell in board.cells_mut() {
+            cell.set_land(Terrain::Plain);
+            cell.reset_cond();
+        }
+
+        Self { config, board, entities: vec![] }
+    }
+
+    /// Seeds the match's random numbers
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = seed;
+        self
+    }
+
+    /// Orders turns by another scheme, under `Initiative` the monster acts first
+    pub fn scheme(mut self, scheme: TurnScheme) -> Self {
+        self.config.turn_scheme = scheme;
+        self
+    }
+
+    /// Lets the Elder God win by outlasting a number of rounds
+    pub fn round_limit(mut self, rounds: u32) -> Self {
+        self.config.round_limit = Some(rounds);
+        self
+    }
+
+    /// Sets the land of a cell
+    pub fn land(mut self, x: usize, y: usize, land: Terrain) -> Self {
+        self.board.cell_mut(Coord::new(x as i32, y as i32)).expect("On the board").set_land(land);
+        self
+    }
+
+    /// Sets the condition of a cell and how many turns it has left
+    pub fn condition(mut self, x: usize, y: usize, condition: TerrainStatus, counter: u32) -> Self {
+        self.board.cell_mut(Coord::new(x as i32, y as i32)).expect("On the board").cond_with_counter(condition, counter);
+        self
+    }
+
+    /// Places an entity, monster classes play for the Elder God and the rest for the hunters
+    /// Entities keep the order they are placed in, so under `Interleaved` the first hunter placed acts first
+    pub fn entity(mut self, class: ClassType, level: u32, x: usize, y: usize) -> Self {
+        let team = match class {
+            ClassType::Wraith | ClassType::Kraken | ClassType::Elder => PlayerType::Player1,
+            _                                                        => PlayerType::Player2,
+        };
+        self.entities.push(Entity::new_char(class, team, level, at(x, y), false).expect("Entity"));
+        self
+    }
+
+    /// Sets the current hp of the last entity
+    pub fn hp(mut self, hp: f32) -> Self {
+        let entity = self.last();
+        let current = *entity.get_curr_stats().expect("Stats").get_hp();
+        entity.add_checked_hp(hp - current).expect("Hp");
+        self
+    }
+
+    /// Replaces the stats of the last entity, it starts at full hp
+    pub fn stats(mut self, hp: f32, speed: f32, armor: f32, power: f32, actions: f32) -> Self {
+        let entity = self.last();
+        entity.set_stats(hp, speed, armor, power, actions, 0.0).expect("Stats");
+        //An effect for no turns changes nothing but recomputes the current stats from the new ones
+        entity.apply_effect(StatusEffect::Marked, 0).expect("Stats");
+        entity.add_checked_hp(hp).expect("Hp");
+        self
+    }
+
+    /// Sets the status of the last entity and the turns it lasts
+    pub fn status(mut self, status: Status, turns: u32) -> Self {
+        self.last().set_status(status, turns).expect("Status");
+        self
+    }
+
+    /// Gives the last entity a status effect for a number of its turns
+    pub fn effect(mut self, effect: StatusEffect, turns: u32) -> Self {
+        self.last().apply_effect(effect, turns).expect("Effect");
+        self
+    }
+
+    pub fn board(&self) -> &GameBoard { &self.board }
+    pub fn entities(&self) -> &Vec<Entity> { &self.entities }
+
+    /// Returns an entity as placed
+    pub fn get(&self, index: usize) -> Entity { self.entities[index] }
+
+    /// Starts a match on the scenario
+    pub fn start(self) -> Match {
+        Match::with_setup(self.config, self.board, self.entities).expect("Match starts")
+    }
+
+    /// Returns the entity placed last
+    fn last(&mut self) -> &mut Entity {
+        self.entities.last_mut().expect("Place an entity first")
+    }
+}
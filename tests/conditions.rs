@@ -0,0 +1,131 @@
+/*
+Rules tests for how land conditions run out over turns and what they do to whoever starts a turn on them.
+*/
+
+mod common;
+
+use common::scenario::{assert_between, cell, hp, Scenario};
+
+use i_am_the_elder_god::gameplay_logic::entities::ClassType;
+use i_am_the_elder_god::gameplay_logic::game_board::GameBoard;
+use i_am_the_elder_god::gameplay_logic::game_match::Command;
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Terrain, TerrainStatus};
+use i_am_the_elder_god::gameplay_logic::turn_order::TurnScheme;
+
+/// Returns the land, condition and counter of a cell
+fn state(board: &GameBoard, x: usize, y: usize) -> (Terrain, TerrainStatus, u32) {
+    let target = cell(board, x, y);
+    (*target.get_land().expect("Land"), *target.get_cond().expect("Cond"), target.get_counter().expect("Counter"))
+}
+
+#[test]
+fn burning_counts_down_then_leaves_destroyed_land() {
+    let mut board = Scenario::open_field().land(3, 3, Terrain::Forest).condition(3, 3, TerrainStatus::Burning, 2).board().clone();
+
+    board.decrement_temp_cond_counters().expect("Counters");
+    assert_eq!(state(&board, 3, 3), (Terrain::Forest, TerrainStatus::Burning, 1));
+    board.decrement_temp_cond_counters().expect("Counters");
+    assert_eq!(state(&board, 3, 3), (Terrain::Destroyed, TerrainStatus::Normal, 0));
+}
+
+#[test]
+fn burning_with_no_turns_left_burns_out_at_once() {
+    let mut board = Scenario::open_field().condition(3, 3, TerrainStatus::Burning, 0).board().clone();
+
+    board.decrement_temp_cond_counters().expect("Counters");
+    assert_eq!(state(&board, 3, 3), (Terrain::Destroyed, TerrainStatus::Normal, 0));
+}
+
+#[test]
+fn frozen_counts_down_then_thaws_leaving_the_land() {
+    let mut board = Scenario::open_field().land(3, 3, Terrain::Forest).condition(3, 3, TerrainStatus::Frozen, 2).board().clone();
+
+    board.decrement_temp_cond_counters().expect("Counters");
+    assert_eq!(state(&board, 3, 3), (Terrain::Forest, TerrainStatus::Frozen, 1));
+    board.decrement_temp_cond_counters().expect("Counters");
+    assert_eq!(state(&board, 3, 3), (Terrain::Forest, TerrainStatus::Normal, 0));
+}
+
+#[test]
+fn shields_and_impassable_space_do_not_count_down() {
+    let mut board = Scenario::open_field()
+        .condition(3, 3, TerrainStatus::Shielded, 2)
+        .land(4, 3, Terrain::Empty).condition(4, 3, TerrainStatus::Impassable, 0)
+        .board().clone();
+
+    for _ in 0..3 { board.decrement_temp_cond_counters().expect("Counters"); }
+    assert_eq!(state(&board, 3, 3), (Terrain::Plain, TerrainStatus::Shielded, 2));
+    assert_eq!(state(&board, 4, 3), (Terrain::Empty, TerrainStatus::Impassable, 0));
+}
+
+#[test]
+fn hits_wear_conditions_down_to_normal() {
+    let mut target = cell(Scenario::open_field().condition(3, 3, TerrainStatus::Shielded, 1).board(), 3, 3);
+
+    target.inc_counter();
+    assert_eq!(target.get_counter().expect("Counter"), 2);
+    target.decr_counter();
+    target.decr_counter();
+    assert_eq!((*target.get_cond().expect("Cond"), target.get_counter().expect("Counter")), (TerrainStatus::Normal, 0));
+
+    //Normal land and spent conditions cannot be strengthened
+    target.inc_counter();
+    assert_eq!(target.get_counter().expect("Counter"), 0);
+    target.cond_with_counter(TerrainStatus::Burning, 0);
+    target.inc_counter();
+    assert_eq!(target.get_counter().expect("Counter"), 0);
+}
+
+#[test]
+fn the_environment_steps_the_same_for_the_same_seed_and_round() {
+    let mut scenario = Scenario::open_field().condition(5, 5, TerrainStatus::Burning, 3).land(5, 6, Terrain::Destroyed);
+    for (x, y) in [(4, 5), (6, 5), (5, 4), (4, 4), (6, 4)].iter() {
+        scenario = scenario.land(*x, *y, Terrain::Forest);
+    }
+    let mut first = scenario.board().clone();
+    let mut second = scenario.board().clone();
+
+    for round in 1..=5 {
+        first.environment_step(42, round).expect("Step");
+        second.environment_step(42, round).expect("Step");
+        assert_eq!(first.snapshot().expect("Snapshot"), second.snapshot().expect("Snapshot"), "Round {}", round);
+    }
+}
+
+#[test]
+fn starting_a_turn_on_fire_burns() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::TeamPhase)
+        .entity(ClassType::Wraith, 1, 5, 5).condition(5, 5, TerrainStatus::Burning, 3)
+        .entity(ClassType::Support, 1, 9, 9)
+        .start();
+    assert!(game.apply(Command::EndTurn).expect("End turn"));
+
+    //A level 1 entity takes 5 to 6 from fire, and the fire has a turn less left
+    assert_between(hp(&game.entities()[0]), 194.0, 195.0);
+    assert_eq!(state(game.board(), 5, 5), (Terrain::Plain, TerrainStatus::Burning, 2));
+}
+
+#[test]
+fn fire_hurts_the_wounded_rather_than_healing_them() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::TeamPhase)
+        .entity(ClassType::Wraith, 1, 5, 5).hp(100.0).condition(5, 5, TerrainStatus::Burning, 3)
+        .entity(ClassType::Support, 1, 9, 9)
+        .start();
+    assert!(game.apply(Command::EndTurn).expect("End turn"));
+
+    //Below max hp a heal would show, the fire must take the same 5 to 6 as at full hp
+    assert_between(hp(&game.entities()[0]), 94.0, 95.0);
+}
+
+#[test]
+fn starting_a_turn_on_ice_stops_movement() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::TeamPhase)
+        .entity(ClassType::Wraith, 1, 5, 5).condition(5, 5, TerrainStatus::Frozen, 3)
+        .entity(ClassType::Support, 1, 9, 9)
+        .start();
+    assert!(game.apply(Command::EndTurn).expect("End turn"));
+
+    assert_eq!(game.current(), 0);
+    assert_eq!(game.moves(), 0);
+    assert_eq!(state(game.board(), 5, 5), (Terrain::Plain, TerrainStatus::Frozen, 2));
+}
@@ -0,0 +1,88 @@
+/*
+Tests for whole number board positions and the bounds-checked board access the rules are built on.
+*/
+
+mod common;
+
+use common::scenario::Scenario;
+
+use i_am_the_elder_god::gameplay_logic::coord::Coord;
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Direction, Terrain};
+
+use quicksilver::geom::Vector;
+
+#[test]
+fn steps_follow_the_board_axes() {
+    let origin = Coord::new(4, 4);
+    assert_eq!(origin.step(Direction::Up), Coord::new(4, 3));
+    assert_eq!(origin.step(Direction::Down), Coord::new(4, 5));
+    assert_eq!(origin.step(Direction::Left), Coord::new(3, 4));
+    assert_eq!(origin.step(Direction::Right), Coord::new(5, 4));
+    assert_eq!(origin.offset(Direction::Left, 6), Coord::new(-2, 4));
+    assert_eq!(origin.distance(Coord::new(1, 6)), 5);
+}
+
+#[test]
+fn neighbours_rings_and_lines_list_the_expected_cells() {
+    let origin = Coord::new(0, 0);
+    assert_eq!(origin.neighbours().collect::<Vec<_>>(),
+               vec![Coord::new(0, -1), Coord::new(-1, 0), Coord::new(0, 1), Coord::new(1, 0)]);
+
+    //Row by row from the top, like the board
+    assert_eq!(origin.ring(1).collect::<Vec<_>>(),
+               vec![Coord::new(0, -1), Coord::new(-1, 0), Coord::new(1, 0), Coord::new(0, 1)]);
+    assert_eq!(origin.within(1).collect::<Vec<_>>(),
+               vec![Coord::new(0, -1), Coord::new(-1, 0), Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1)]);
+    assert_eq!(origin.ring(0).collect::<Vec<_>>(), vec![origin]);
+
+    for radius in 1..5 {
+        assert_eq!(origin.ring(radius).count() as u32, radius * 4);
+        assert!(origin.ring(radius).all(|coord| coord.distance(origin) == radius));
+        assert!(origin.within(radius).all(|coord| coord.distance(origin) <= radius));
+    }
+
+    assert_eq!(origin.line(Direction::Down).take(3).collect::<Vec<_>>(),
+               vec![Coord::new(0, 1), Coord::new(0, 2), Coord::new(0, 3)]);
+}
+
+#[test]
+fn vectors_convert_to_the_cell_they_stand_for() {
+    assert_eq!(Coord::from(Vector::new(3.0, 7.0)), Coord::new(3, 7));
+    assert_eq!(Coord::from(Vector::new(2.9999, 7.0001)), Coord::new(3, 7));
+    assert_eq!(Coord::from(Vector::new(-1.0, 0.0)), Coord::new(-1, 0));
+    assert_eq!(Vector::from(Coord::new(3, 7)), Vector::new(3.0, 7.0));
+}
+
+#[test]
+fn board_access_is_checked_against_the_edges() {
+    let scenario = Scenario::open_field().land(2, 1, Terrain::Wall);
+    let board = scenario.board();
+    let (width, height) = (board.width() as i32, board.height() as i32);
+
+    assert_eq!(*board.get(Coord::new(2, 1)).expect("On the board").get_land().expect("Land"), Terrain::Wall);
+    assert_eq!(board.cell(Coord::new(2, 1)).expect("On the board").get_pos().expect("Pos"), Vector::new(2.0, 1.0));
+
+    for off in [Coord::new(-1, 0), Coord::new(0, -1), Coord::new(width, 0), Coord::new(0, height)].iter() {
+        assert!(board.get(*off).is_none(), "{:?} is off the board", off);
+        assert!(!board.contains(*off));
+        assert!(board.cell(*off).is_err());
+    }
+    assert!(board.contains(Coord::new(width - 1, height - 1)));
+
+    //Every cell once, in the order the board is stored
+    let coords = board.coords().collect::<Vec<_>>();
+    assert_eq!(coords.len() as i32, width * height);
+    assert_eq!(coords[0], Coord::new(0, 0));
+    assert_eq!(coords[1], Coord::new(1, 0));
+    assert_eq!(coords[width as usize], Coord::new(0, 1));
+}
+
+#[test]
+fn board_cells_can_be_changed_through_a_position() {
+    let mut board = Scenario::open_field().board().clone();
+    board.get_mut(Coord::new(5, 5)).expect("On the board").set_land(Terrain::Forest);
+    assert_eq!(*board.cell(Coord::new(5, 5)).expect("On the board").get_land().expect("Land"), Terrain::Forest);
+
+    assert!(board.get_mut(Coord::new(-3, 5)).is_none());
+    assert!(board.cell_mut(Coord::new(5, 500)).is_err());
+}
@@ -0,0 +1,29 @@
+/*
+Tests for ability effects keeping time with their delay.
+*/
+
+use i_am_the_elder_god::gameplay_logic::animator::{Easing, Tween};
+use i_am_the_elder_god::gameplay_logic::effects::{Effect, EffectShape};
+
+use quicksilver::geom::Vector;
+use quicksilver::graphics::Color;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn delayed_paths_start_from_their_beginning() {
+    let arrived = Rc::new(Cell::new(false));
+    let reported = Rc::clone(&arrived);
+    let path = Tween::new(Vector::new(0.0, 0.0), Vector::new(4.0, 0.0), 0.5, Easing::Linear).expect("Tween")
+        .with_callback(Box::new(move || reported.set(true)));
+    let mut effect = Effect::new(EffectShape::Particle { path }, Color::WHITE, 0.5, 0.5);
+
+    //Half of this is spent waiting, so the path is only halfway along
+    effect.update(0.75);
+    assert!(!arrived.get());
+    assert!(!effect.is_finished());
+
+    effect.update(0.25);
+    assert!(arrived.get());
+    assert!(effect.is_finished());
+}
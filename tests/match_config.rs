@@ -0,0 +1,49 @@
+/*
+Tests that a match config from outside the setup scene, such as one a client sends a server, is refused
+with an error instead of stopping the game when it names something that does not exist.
+*/
+
+use i_am_the_elder_god::gameplay_logic::entities::ClassType;
+use i_am_the_elder_god::gameplay_logic::game_board::GameBoard;
+use i_am_the_elder_god::gameplay_logic::game_match::Match;
+use i_am_the_elder_god::gameplay_logic::match_config::{MatchConfig, MAX_HUNTER_LEVEL, MONSTER_STAGES};
+
+#[test]
+fn every_choice_the_setup_scene_offers_is_accepted() {
+    for level in 1..=2 {
+        for hunter_level in 1..=MAX_HUNTER_LEVEL {
+            for monster_stage in MONSTER_STAGES.iter() {
+                let config = MatchConfig { level, hunter_level, monster_stage: *monster_stage, ..MatchConfig::default() };
+                assert!(config.check().is_ok(), "{:?}", config);
+                assert!(Match::new(config).is_ok(), "{:?}", config);
+            }
+        }
+    }
+}
+
+#[test]
+fn unknown_levels_are_refused() {
+    for level in [0, 3, usize::MAX].iter() {
+        let config = MatchConfig { level: *level, ..MatchConfig::default() };
+        assert!(config.check().is_err());
+        assert!(config.create_entities().is_err());
+        assert!(GameBoard::new(*level).is_err());
+        assert!(Match::new(config).is_err());
+    }
+}
+
+#[test]
+fn hunter_levels_outside_the_setup_range_are_refused() {
+    for hunter_level in [0, MAX_HUNTER_LEVEL + 1, u32::MAX].iter() {
+        let config = MatchConfig { hunter_level: *hunter_level, ..MatchConfig::default() };
+        assert!(config.check().is_err());
+        assert!(Match::new(config).is_err());
+    }
+}
+
+#[test]
+fn hunters_cannot_be_the_monster() {
+    let config = MatchConfig { monster_stage: ClassType::Assault, ..MatchConfig::default() };
+    assert!(config.check().is_err());
+    assert!(Match::new(config).is_err());
+}
@@ -0,0 +1,59 @@
+/*
+Tests for the lines of JSON sent between network players: a peer that floods or stops reading is given up on
+rather than filling memory or stalling the game loop.
+*/
+
+use i_am_the_elder_god::game_logic::network::Link;
+
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Returns a link and the raw stream at its other end
+fn pair() -> (Link, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Bind");
+    let raw = TcpStream::connect(listener.local_addr().expect("Address")).expect("Connect");
+    let (stream, _) = listener.accept().expect("Accept");
+    (Link::new(stream).expect("Link"), raw)
+}
+
+#[test]
+fn messages_arrive_once_their_line_ends() {
+    let (mut link, mut raw) = pair();
+
+    raw.write_all(b"[1,2").expect("Write");
+    sleep(Duration::from_millis(50));
+    assert!(link.receive::<Vec<u32>>().expect("Receive").is_empty());
+
+    raw.write_all(b",3]\nnot json\n[4]\n").expect("Write");
+    sleep(Duration::from_millis(50));
+    assert_eq!(link.receive::<Vec<u32>>().expect("Receive"), vec![vec![1, 2, 3], vec![4]]);
+}
+
+#[test]
+fn a_line_that_never_ends_drops_the_peer() {
+    let (mut link, mut raw) = pair();
+    let flood = vec![b' '; 64 * 1024];
+
+    let mut dropped = false;
+    for _ in 0..64 {
+        if raw.write_all(&flood).is_err() { break; }
+        sleep(Duration::from_millis(5));
+        if link.receive::<u32>().is_err() { dropped = true; break; }
+    }
+    assert!(dropped);
+}
+
+#[test]
+fn a_peer_that_stops_reading_does_not_block_sending() {
+    let (mut link, _raw) = pair();
+    let message = vec![7u32; 64 * 1024];
+
+    //Each send returns at once, the queue only fills until the peer is given up on
+    let mut gave_up = false;
+    for _ in 0..200 {
+        if link.send(&message).is_err() { gave_up = true; break; }
+    }
+    assert!(gave_up);
+}
@@ -0,0 +1,131 @@
+/*
+Tests for taking in turn files: a file is only accepted from the other team, for a match that can be set up,
+and when the rules replaying its commands end in the match it holds, whatever its stamp says. Mailboxes keep
+why a file was refused and start a new chain for the match after a finished one.
+*/
+
+use i_am_the_elder_god::game_logic::play_by_mail::{Mailbox, TurnChain, TurnFile};
+use i_am_the_elder_god::gameplay_logic::entities::PlayerType;
+use i_am_the_elder_god::gameplay_logic::game_match::{Match, Command};
+use i_am_the_elder_god::gameplay_logic::match_config::MatchConfig;
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// Has the team to play end turns until the turn passes, returning that team and the turn file written for it
+fn play_turn(chain: &mut TurnChain, game: &mut Match) -> (PlayerType, String) {
+    let team = game.team().expect("Team");
+    loop {
+        assert!(game.apply(Command::EndTurn).expect("End turn"));
+        chain.record(team, Command::EndTurn);
+        if chain.turn_over(game).expect("Turn over") { break; }
+    }
+    (team, chain.export(game).expect("Export"))
+}
+
+/// Replaces text in a turn file and stamps it again, as anyone editing a file can
+fn edit(text: &str, from: &str, to: &str) -> String {
+    let (body, _) = text.trim_end().rsplit_once('\n').expect("Stamp");
+    let body = format!("{}\n", body).replacen(from, to, 1);
+    let hash: String = Sha256::digest(body.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+    let edited = format!("{}hash {}\n", body, hash);
+    TurnFile::from_text(&edited).expect("The stamp matches again");
+    edited
+}
+
+/// Returns an empty mail folder for a test
+fn mail_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("elder-god-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&folder);
+    folder
+}
+
+/// Returns the team the other player plays
+fn other(team: PlayerType) -> PlayerType {
+    if team == PlayerType::Player1 { PlayerType::Player2 } else { PlayerType::Player1 }
+}
+
+#[test]
+fn the_other_screen_takes_a_turn_in() {
+    let config = MatchConfig::default();
+    let mut game = Match::new(config).expect("Match");
+    let (team, text) = play_turn(&mut TurnChain::new(&config).expect("Chain"), &mut game);
+
+    let mut reader = TurnChain::new(&config).expect("Chain");
+    let (taken, _) = reader.import(&text, &Match::new(config).expect("Match"), other(team)).expect("Import");
+    assert_eq!(taken.snapshot().expect("Snapshot"), game.snapshot().expect("Snapshot"));
+    assert_eq!(reader.sequence(), 1);
+}
+
+#[test]
+fn a_turn_claiming_to_play_this_screens_team_is_refused() {
+    let config = MatchConfig::default();
+    let mut game = Match::new(config).expect("Match");
+    let (team, text) = play_turn(&mut TurnChain::new(&config).expect("Chain"), &mut game);
+
+    let start = Match::new(config).expect("Match");
+    let mut reader = TurnChain::new(&config).expect("Chain");
+    assert!(reader.import(&text, &start, team).is_err());
+    assert_eq!(reader.sequence(), 0);
+
+    //Picking a match back up takes in this screen's own turns
+    assert!(reader.import(&text, &start, PlayerType::Undetermined).is_ok());
+}
+
+#[test]
+fn a_first_turn_setting_up_a_match_that_cannot_exist_is_refused() {
+    let config = MatchConfig::default();
+    let mut game = Match::new(config).expect("Match");
+    let (team, text) = play_turn(&mut TurnChain::new(&config).expect("Chain"), &mut game);
+
+    for (from, to) in [("\"level\":1", "\"level\":99"), ("\"hunter_level\":1", "\"hunter_level\":0")].iter() {
+        let edited = edit(&text, from, to);
+        let mut reader = TurnChain::new(&config).expect("Chain");
+        assert!(reader.import(&edited, &Match::new(config).expect("Match"), other(team)).is_err(), "{}", to);
+    }
+}
+
+#[test]
+fn an_edited_turn_stamped_again_is_caught_by_the_replay() {
+    let config = MatchConfig::default();
+    let mut game = Match::new(config).expect("Match");
+    let (team, text) = play_turn(&mut TurnChain::new(&config).expect("Chain"), &mut game);
+
+    let edited = edit(&text, "\"winner\":\"Undetermined\"", &format!("\"winner\":\"{:?}\"", team));
+    let mut reader = TurnChain::new(&config).expect("Chain");
+    assert!(reader.import(&edited, &Match::new(config).expect("Match"), other(team)).is_err());
+}
+
+#[test]
+fn a_refused_turn_file_keeps_its_reason() {
+    let (config, folder) = (MatchConfig::default(), mail_folder("refusal"));
+    let mut mailbox = Mailbox::open(&folder, config.player_one, &config).expect("Mailbox");
+    fs::write(folder.join("match-0001-turn-0001.txt"), "not a turn file\n").expect("Write");
+
+    assert!(mailbox.poll(&Match::new(config).expect("Match")).expect("Poll").is_none());
+    assert!(mailbox.refusal().expect("Refusal").starts_with("match-0001-turn-0001.txt was refused"));
+    fs::remove_dir_all(&folder).expect("Clean up");
+}
+
+#[test]
+fn the_match_after_a_finished_one_starts_a_new_chain() {
+    let (config, folder) = (MatchConfig::default(), mail_folder("rematch"));
+    let mut game = Match::new(config).expect("Match");
+    let team = game.team().expect("Team");
+    let mut mailbox = Mailbox::open(&folder, team, &config).expect("Mailbox");
+    assert!(game.apply(Command::Concede(team)).expect("Concede"));
+    mailbox.record(Command::Concede(team), &game).expect("Record");
+    assert!(folder.join("match-0001-turn-0001.txt").exists());
+
+    //The finished match's file is still there but the next match never takes it in
+    let start = Match::new(config).expect("Match");
+    let mut next = mailbox.reopen(team, &config).expect("Reopen");
+    assert!(next.poll(&start).expect("Poll").is_none());
+    assert!(next.refusal().is_none());
+
+    //Opening the folder again carries on the latest match, which is still the finished one
+    let mut resumed = Mailbox::open(&folder, team, &config).expect("Mailbox");
+    assert!(resumed.poll(&start).expect("Poll").is_some());
+    fs::remove_dir_all(&folder).expect("Clean up");
+}
@@ -0,0 +1,189 @@
+/*
+Plays random commands on random boards and checks after every command that the rules never break what must
+always hold: hp stays between 0 and max, no two bodies share a cell, nobody stands on empty space, condition
+counters never wrap below zero, targeting stays on the board and a match with a round limit always ends.
+*/
+
+mod common;
+
+use common::scenario::Scenario;
+
+use i_am_the_elder_god::gameplay_logic::coord::Coord;
+use i_am_the_elder_god::gameplay_logic::entities::{ClassType, PlayerType};
+use i_am_the_elder_god::gameplay_logic::game_match::{Command, Match};
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Direction, Terrain, TerrainStatus};
+use i_am_the_elder_god::gameplay_logic::match_config::MONSTER_STAGES;
+use i_am_the_elder_god::gameplay_logic::turn_order::TURN_SCHEMES;
+
+use proptest::prelude::*;
+use proptest::sample::{select, Index};
+use quicksilver::geom::Vector;
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Left, Direction::Down];
+/// Every counter starts far below this, one that wrapped below zero would be far above it
+const COUNTER_CEILING: u32 = 1_000;
+
+fn land() -> impl Strategy<Value = Terrain> {
+    prop_oneof![
+        6 => Just(Terrain::Plain),
+        2 => Just(Terrain::Road),
+        2 => Just(Terrain::Forest),
+        1 => Just(Terrain::Mountain),
+        1 => Just(Terrain::City),
+        1 => Just(Terrain::Wall),
+        1 => Just(Terrain::Pit),
+        1 => Just(Terrain::Spikes),
+        1 => Just(Terrain::Destroyed),
+        1 => Just(Terrain::Empty),
+    ]
+}
+
+fn condition() -> impl Strategy<Value = (TerrainStatus, u32)> {
+    prop_oneof![
+        6 => Just((TerrainStatus::Normal, 0)),
+        1 => (1..=6u32).prop_map(|counter| (TerrainStatus::Burning, counter)),
+        1 => (1..=6u32).prop_map(|counter| (TerrainStatus::Frozen, counter)),
+        1 => (1..=3u32).prop_map(|counter| (TerrainStatus::Shielded, counter)),
+    ]
+}
+
+fn command() -> impl Strategy<Value = Command> {
+    prop_oneof![
+        4 => select(DIRECTIONS.to_vec()).prop_map(Command::Move),
+        2 => (1..=3u32, select(DIRECTIONS.to_vec())).prop_map(|(ability, direction)| Command::Act(ability, direction)),
+        1 => Just(Command::EndTurn),
+    ]
+}
+
+/// Everything a random match is made from
+#[derive(Debug, Clone)]
+struct Setup {
+    cells: Vec<(Terrain, (TerrainStatus, u32))>, //Row by row
+    spawns: Vec<Index>, //Picks from the cells that can be stood on, monster first
+    monster: ClassType,
+    levels: Vec<u32>, //Support, Assault and Trapper
+    scheme: usize,
+    round_limit: u32,
+    seed: u64,
+}
+
+fn setup() -> impl Strategy<Value = Setup> {
+    let board = Scenario::open_field().board().cells().count();
+    (
+        proptest::collection::vec((land(), condition()), board),
+        proptest::collection::vec(any::<Index>(), 4),
+        select(MONSTER_STAGES.to_vec()),
+        proptest::collection::vec(1..=3u32, 3),
+        0..TURN_SCHEMES.len(),
+        1..=6u32,
+        any::<u64>(),
+    ).prop_map(|(cells, spawns, monster, levels, scheme, round_limit, seed)| Setup { cells, spawns, monster, levels, scheme, round_limit, seed })
+}
+
+/// Builds the match a setup describes, or None if there is not enough land to stand everyone on
+fn start(setup: &Setup) -> Option<Match> {
+    let mut scenario = Scenario::open_field().seed(setup.seed).scheme(TURN_SCHEMES[setup.scheme]).round_limit(setup.round_limit);
+    let width = scenario.board().width();
+
+    let mut standable = vec![];
+    for (index, (land, (condition, counter))) in setup.cells.iter().enumerate() {
+        let (x, y) = (index % width, index / width);
+        scenario = match land {
+            Terrain::Empty => scenario.land(x, y, *land).condition(x, y, TerrainStatus::Impassable, 0),
+            _              => scenario.land(x, y, *land).condition(x, y, *condition, *counter),
+        };
+        if land.rules().move_cost.is_some() { standable.push((x, y)); }
+    }
+
+    let classes = [setup.monster, ClassType::Support, ClassType::Assault, ClassType::Trapper];
+    let levels = [1, setup.levels[0], setup.levels[1], setup.levels[2]];
+    for ((class, level), spawn) in classes.iter().zip(levels.iter()).zip(setup.spawns.iter()) {
+        if standable.is_empty() { return None; }
+        let (x, y) = standable.remove(spawn.index(standable.len()));
+        scenario = scenario.entity(*class, *level, x, y);
+    }
+
+    Some(scenario.start())
+}
+
+
+/// Returns true if a position is a cell of the board
+fn on_board(game: &Match, pos: Vector) -> bool {
+    pos.x >= 0.0 && pos.y >= 0.0 && game.board().contains(Coord::from(pos))
+}
+
+/// Checks everything that must hold between any two commands
+fn check_invariants(game: &Match) -> Result<(), TestCaseError> {
+    let board = game.board();
+
+    for (index, entity) in game.entities().iter().enumerate() {
+        let mut entity = *entity;
+        let max_hp = *entity.get_stats().expect("Stats").get_hp();
+        let hp = *entity.get_curr_stats().expect("Stats").get_hp();
+        prop_assert!(hp >= 0.0 && hp <= max_hp, "Entity {} has {} of {} hp", index, hp, max_hp);
+
+        let pos = entity.get_pos().expect("Pos");
+        prop_assert!(on_board(game, pos), "Entity {} is off the board at {:?}", index, pos);
+        prop_assert!(*board.cell(Coord::from(pos)).expect("On the board").get_land().expect("Land") != Terrain::Empty,
+                     "Entity {} stands on empty space at {:?}", index, pos);
+
+        if entity.is_dead() || !entity.get_tangible().expect("Tangible") { continue; }
+        for (other_index, other) in game.entities().iter().enumerate().skip(index + 1) {
+            let solid = !other.is_dead() && other.get_tangible().expect("Tangible");
+            prop_assert!(!solid || other.get_pos().expect("Pos") != pos, "Entities {} and {} share {:?}", index, other_index, pos);
+        }
+    }
+
+    for cell in board.cells() {
+        let counter = cell.get_counter().expect("Counter");
+        prop_assert!(counter < COUNTER_CEILING, "Counter wrapped to {} at {:?}", counter, cell.get_pos().expect("Pos"));
+        if *cell.get_cond().expect("Cond") == TerrainStatus::Normal {
+            prop_assert_eq!(counter, 0, "Normal land kept a counter at {:?}", cell.get_pos().expect("Pos"));
+        }
+    }
+
+    //Whoever is about to act can only ever aim at and reach cells of the board
+    if game.winner() == PlayerType::Undetermined {
+        let entity = game.current_entity();
+        for ability in 1..=3 {
+            for direction in DIRECTIONS.iter() {
+                let (targets, _) = entity.act(ability, *direction, game.board(), game.entities()).expect("Targets");
+                for target in targets {
+                    prop_assert!(on_board(game, target), "Ability {} aimed {:?} targets {:?}", ability, direction, target);
+                }
+            }
+        }
+        for pos in entity.reachable(game.moves(), game.board(), game.entities()).expect("Reachable") {
+            prop_assert!(on_board(game, pos), "Can reach {:?}", pos);
+        }
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn random_play_keeps_the_rules_whole(setup in setup(), commands in proptest::collection::vec(command(), 0..150)) {
+        let mut game = match start(&setup) {
+            Some(game) => game,
+            None       => return Ok(()),
+        };
+        check_invariants(&game)?;
+
+        for command in commands {
+            game.apply(command).expect("Command");
+            check_invariants(&game)?;
+        }
+
+        //A round deals fewer turns than twice the number of entities, so the round limit is passed within this many
+        let turn_bound = (setup.round_limit + 1) * 2 * game.entities().len() as u32;
+        for _ in 0..turn_bound {
+            if game.winner() != PlayerType::Undetermined { break; }
+            game.apply(Command::EndTurn).expect("End turn");
+            check_invariants(&game)?;
+        }
+        prop_assert_ne!(game.winner(), PlayerType::Undetermined, "No winner after {} more turns", turn_bound);
+    }
+}
@@ -0,0 +1,73 @@
+/*
+Regression tests for what abilities do to the board, recorded as snapshot diffs in golden files.
+*/
+
+mod common;
+
+use common::assert_golden;
+
+use i_am_the_elder_god::gameplay_logic::coord::Coord;
+use i_am_the_elder_god::gameplay_logic::entities::{Entity, ClassType, PlayerType};
+use i_am_the_elder_god::gameplay_logic::game_board::GameBoard;
+use i_am_the_elder_god::gameplay_logic::game_match::{Match, Command};
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Direction, Terrain, TerrainStatus};
+use i_am_the_elder_god::gameplay_logic::match_config::MatchConfig;
+use i_am_the_elder_god::gameplay_logic::snapshot::Snapshot;
+
+use quicksilver::geom::Vector;
+
+/// Starts a match on level 1 with bare land where a level 2 Assault, the first to have Grenade, stands at (5, 8) and acts first
+/// Thrown up, the Grenade lands on (5, 5) and reaches the plains around it, which start out normal, frozen and burning
+fn grenade_match() -> Match {
+    let config = MatchConfig { seed: 7, ..MatchConfig::default() };
+    let mut board = GameBoard::new(config.level).expect("Level 1 exists");
+    board.clear_conditions().expect("Conditions can be cleared");
+
+    let monster = Entity::new_char(ClassType::Wraith, PlayerType::Player1, 1, Vector::new(9.0, 12.0), false).expect("Wraith");
+    let assault = Entity::new_char(ClassType::Assault, PlayerType::Player2, 2, Vector::new(5.0, 8.0), false).expect("Assault");
+    let entities = vec![monster, assault];
+
+    let conditions = [((4, 5), TerrainStatus::Normal, 0), ((6, 5), TerrainStatus::Frozen, 2), ((5, 6), TerrainStatus::Burning, 2)];
+    for ((x, y), condition, counter) in conditions.iter() {
+        let cell = board.get_mut(Coord::new(*x, *y)).expect("On the board");
+        cell.set_land(Terrain::Plain);
+        cell.cond_with_counter(*condition, *counter);
+    }
+
+    Match::with_setup(config, board, entities).expect("Match starts")
+}
+
+#[test]
+fn grenade_ignites_melts_and_intensifies() {
+    let mut game = grenade_match();
+    let before = game.snapshot().expect("Snapshot");
+    assert!(game.apply(Command::Act(2, Direction::Up)).expect("Grenade"));
+    let after = game.snapshot().expect("Snapshot");
+
+    assert_golden("grenade_up", &before.diff_text(&after));
+}
+
+#[test]
+fn snapshot_text_reads_back_the_same() {
+    let game = grenade_match();
+    let snapshot = game.snapshot().expect("Snapshot");
+    assert_eq!(Snapshot::parse(&snapshot.to_string()), snapshot);
+}
+
+#[test]
+fn identical_snapshots_have_no_changes() {
+    let game = grenade_match();
+    assert!(game.snapshot().expect("Snapshot").diff(&game.clone().snapshot().expect("Snapshot")).is_empty());
+}
+
+#[test]
+fn diff_reports_every_changed_field() {
+    let mut game = grenade_match();
+    let before = Snapshot::of_entities(game.entities()).expect("Snapshot");
+    assert!(game.apply(Command::Move(Direction::Up)).expect("Move"));
+    let changes = before.diff(&Snapshot::of_entities(game.entities()).expect("Snapshot"));
+
+    let fields: Vec<(&str, &str)> = changes.iter().map(|change| (change.record.as_str(), change.field.as_str())).collect();
+    assert_eq!(fields, vec![("entity 1", "pos.y")]);
+    assert_eq!(changes[0].to_string(), "entity 1 pos.y: 8.0 -> 7.0");
+}
@@ -0,0 +1,220 @@
+/*
+Rules tests for the cells each targeting helper of an entity picks out. Helpers are called on scenarios built
+by hand, every helper is covered on open ground, next to the board's edges and around obstacles.
+*/
+
+mod common;
+
+use common::scenario::{at, coords, sorted_coords, Scenario};
+
+use i_am_the_elder_god::gameplay_logic::entities::ClassType;
+use i_am_the_elder_god::gameplay_logic::gameplay_type::{Direction, Terrain, TerrainStatus};
+
+#[test]
+fn can_attack_anything_on_the_board() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5)
+        .land(6, 5, Terrain::Wall).land(4, 5, Terrain::Empty);
+    let trapper = scenario.get(0);
+
+    assert!(trapper.can_attack(at(6, 5), scenario.board(), scenario.entities()));
+    assert!(trapper.can_attack(at(5, 5), scenario.board(), scenario.entities()));
+    assert!(!trapper.can_attack(at(4, 5), scenario.board(), scenario.entities()));
+}
+
+#[test]
+fn can_move_onto_open_land_only() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5).entity(ClassType::Wraith, 1, 5, 4)
+        .land(6, 5, Terrain::Wall).land(4, 5, Terrain::Empty).condition(5, 6, TerrainStatus::Frozen, 2)
+        .condition(7, 5, TerrainStatus::Burning, 2);
+    let (trapper, board, entities) = (scenario.get(0), scenario.board(), scenario.entities());
+
+    assert!(!trapper.can_move(at(5, 4), board, entities).expect("Move"));
+    assert!(!trapper.can_move(at(6, 5), board, entities).expect("Move"));
+    assert!(!trapper.can_move(at(4, 5), board, entities).expect("Move"));
+    assert!(!trapper.can_move(at(5, 6), board, entities).expect("Move"));
+    assert!(trapper.can_move(at(7, 5), board, entities).expect("Move"));
+}
+
+#[test]
+fn mountains_take_two_whole_moves_to_climb() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5).land(5, 4, Terrain::Mountain);
+    let (trapper, board, entities) = (scenario.get(0), scenario.board(), scenario.entities());
+
+    //Moves are counted in halves, so one full move is 2 and a mountain costs 4
+    assert!(!trapper.reachable(2, board, entities).expect("Reachable").contains(&at(5, 4)));
+    assert!(!trapper.reachable(3, board, entities).expect("Reachable").contains(&at(5, 4)));
+    assert!(trapper.reachable(4, board, entities).expect("Reachable").contains(&at(5, 4)));
+}
+
+#[test]
+fn spikes_take_one_move_to_enter_and_a_second_if_there_is_one() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5).land(5, 4, Terrain::Spikes);
+    let (trapper, board, entities) = (scenario.get(0), scenario.board(), scenario.entities());
+    let spikes = Terrain::Spikes.rules();
+
+    //A single move left is enough to step on, the second move is only taken if there is one
+    assert!(trapper.reachable(2, board, entities).expect("Reachable").contains(&at(5, 4)));
+    assert_eq!((spikes.moves_after(2), spikes.moves_after(3), spikes.moves_after(4), spikes.moves_after(6)), (0, 0, 0, 2));
+}
+
+#[test]
+fn can_grapple_bodies_walls_mountains_and_barriers() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5).entity(ClassType::Wraith, 1, 5, 2)
+        .land(6, 2, Terrain::Wall).land(7, 2, Terrain::Mountain).land(8, 2, Terrain::Forest)
+        .condition(9, 2, TerrainStatus::Shielded, 1).condition(10, 2, TerrainStatus::Frozen, 1);
+    let (trapper, board, entities) = (scenario.get(0), scenario.board(), scenario.entities());
+
+    let grappable: Vec<bool> = (4..=10).map(|x| trapper.can_grapple(at(x, 2), board, entities)).collect();
+    assert_eq!(grappable, vec![false, true, true, true, false, true, true]);
+}
+
+#[test]
+fn list_range_ally_finds_every_teammate_including_the_caster() {
+    let scenario = Scenario::open_field().entity(ClassType::Wraith, 1, 8, 8)
+        .entity(ClassType::Support, 2, 2, 2).entity(ClassType::Assault, 1, 4, 4).entity(ClassType::Trapper, 1, 6, 6);
+
+    let support = scenario.get(1);
+    assert_eq!(coords(&support.list_range_ally(scenario.board(), scenario.entities()).expect("Allies")),
+               vec![(2, 2), (4, 4), (6, 6)]);
+    let wraith = scenario.get(0);
+    assert_eq!(coords(&wraith.list_range_ally(scenario.board(), scenario.entities()).expect("Allies")), vec![(8, 8)]);
+}
+
+#[test]
+fn adjacent_range_leaves_out_the_caster() {
+    let scenario = Scenario::open_field().entity(ClassType::Wraith, 1, 5, 5);
+    let targets = scenario.get(0).adjacent_range(1, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(5, 4), (4, 5), (6, 5), (5, 6)]);
+}
+
+#[test]
+fn adjacent_radial_includes_the_caster() {
+    let scenario = Scenario::open_field().entity(ClassType::Support, 1, 5, 5);
+    let targets = scenario.get(0).adjacent_radial(1, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(5, 4), (4, 5), (5, 5), (6, 5), (5, 6)]);
+}
+
+#[test]
+fn adjacent_shell_is_the_ring_at_exactly_the_range() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 3, 5, 5);
+    let targets = scenario.get(0).adjacent_shell(3, scenario.board(), scenario.entities()).expect("Targets");
+
+    assert_eq!(targets.len(), 12);
+    assert!(targets.iter().all(|pos| (pos.x - 5.0).abs() + (pos.y - 5.0).abs() == 3.0));
+}
+
+#[test]
+fn adjacent_shell_is_cut_by_the_board_edges_and_empty_space() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 3, 1, 1).land(4, 1, Terrain::Empty);
+    let targets = scenario.get(0).adjacent_shell(3, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(3, 0), (3, 2), (0, 3), (2, 3), (1, 4)]);
+}
+
+#[test]
+fn radial_range_is_centred_on_the_location_given() {
+    let scenario = Scenario::open_field().entity(ClassType::Assault, 1, 9, 9);
+    let targets = scenario.get(0).radial_range(at(0, 0), 1, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(0, 0), (1, 0), (0, 1)]);
+}
+
+#[test]
+fn directed_line_range_stops_at_walls() {
+    let open = Scenario::open_field().entity(ClassType::Assault, 1, 5, 5);
+    let targets = open.get(0).directed_line_range(2, Direction::Up, open.board(), open.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(5, 4), (5, 3)]);
+
+    let walled = Scenario::open_field().entity(ClassType::Assault, 1, 5, 5).land(5, 4, Terrain::Wall);
+    let targets = walled.get(0).directed_line_range(2, Direction::Up, walled.board(), walled.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(5, 4)]);
+}
+
+#[test]
+fn directed_line_range_never_reaches_the_outer_cells() {
+    let scenario = Scenario::open_field().entity(ClassType::Assault, 1, 5, 2);
+    let targets = scenario.get(0).directed_line_range(2, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(5, 1)]);
+}
+
+#[test]
+fn directed_line_range_skips_entities_hidden_in_forest() {
+    let scenario = Scenario::open_field().entity(ClassType::Assault, 1, 5, 5).entity(ClassType::Wraith, 1, 5, 4)
+        .land(5, 4, Terrain::Forest);
+    let targets = scenario.get(0).directed_line_range(2, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(5, 3)]);
+}
+
+#[test]
+fn directed_line_cast_is_truncated_before_every_edge() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 3).entity(ClassType::Trapper, 2, 16, 12);
+    let (near, far, board, entities) = (scenario.get(0), scenario.get(1), scenario.board(), scenario.entities());
+
+    assert_eq!(coords(&near.directed_line_cast(6, Direction::Up, board, entities).expect("Targets")), vec![(3, 2), (3, 1)]);
+    assert_eq!(coords(&near.directed_line_cast(6, Direction::Left, board, entities).expect("Targets")), vec![(2, 3), (1, 3)]);
+    assert_eq!(coords(&far.directed_line_cast(6, Direction::Right, board, entities).expect("Targets")), vec![(17, 12)]);
+    assert_eq!(coords(&far.directed_line_cast(6, Direction::Down, board, entities).expect("Targets")), vec![(16, 13)]);
+}
+
+#[test]
+fn directed_line_cast_from_an_edge_reaches_nothing() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 0);
+    let targets = scenario.get(0).directed_line_cast(6, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+    assert!(targets.is_empty());
+}
+
+#[test]
+fn directed_line_cast_stops_at_the_first_thing_it_can_grapple() {
+    let body = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 8).entity(ClassType::Wraith, 1, 3, 5);
+    let targets = body.get(0).directed_line_cast(6, Direction::Up, body.board(), body.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(3, 7), (3, 6), (3, 5)]);
+
+    let ice = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 8).condition(3, 6, TerrainStatus::Frozen, 2);
+    let targets = ice.get(0).directed_line_cast(6, Direction::Up, ice.board(), ice.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(3, 7), (3, 6)]);
+
+    let wall = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 8).land(3, 7, Terrain::Wall);
+    let targets = wall.get(0).directed_line_cast(6, Direction::Up, wall.board(), wall.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(3, 7)]);
+}
+
+#[test]
+fn directed_line_cast_stops_short_of_empty_space() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 2, 3, 8).land(3, 6, Terrain::Empty);
+    let targets = scenario.get(0).directed_line_cast(6, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(coords(&targets), vec![(3, 7)]);
+}
+
+#[test]
+fn directed_line_radial_cast_lands_at_full_range_on_open_ground() {
+    let scenario = Scenario::open_field().entity(ClassType::Assault, 2, 5, 8);
+    let targets = scenario.get(0).directed_line_radial_cast(3, 1, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(5, 4), (4, 5), (5, 5), (6, 5), (5, 6)]);
+}
+
+#[test]
+fn directed_line_radial_cast_lands_in_front_of_obstacles() {
+    let wall = Scenario::open_field().entity(ClassType::Assault, 2, 5, 8).land(5, 6, Terrain::Wall);
+    let targets = wall.get(0).directed_line_radial_cast(3, 1, Direction::Up, wall.board(), wall.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(5, 6), (4, 7), (5, 7), (6, 7), (5, 8)]);
+
+    //Right up against a body the blast lands on the caster
+    let body = Scenario::open_field().entity(ClassType::Assault, 2, 5, 8).entity(ClassType::Wraith, 1, 5, 7);
+    let targets = body.get(0).directed_line_radial_cast(3, 1, Direction::Up, body.board(), body.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(5, 7), (4, 8), (5, 8), (6, 8), (5, 9)]);
+}
+
+#[test]
+fn directed_line_radial_leaves_out_the_caster() {
+    let scenario = Scenario::open_field().entity(ClassType::Trapper, 1, 5, 5);
+    let targets = scenario.get(0).directed_line_radial(1, 1, Direction::Right, scenario.board(), scenario.entities()).expect("Targets");
+    assert_eq!(sorted_coords(&targets), vec![(6, 4), (6, 5), (7, 5), (6, 6)]);
+}
+
+#[test]
+fn directed_line_radial_sky_cannot_hit_cities() {
+    let scenario = Scenario::open_field().entity(ClassType::Assault, 3, 5, 12).land(5, 9, Terrain::City);
+    let targets = scenario.get(0).directed_line_radial_sky(3, 3, Direction::Up, scenario.board(), scenario.entities()).expect("Targets");
+
+    assert_eq!(targets.len(), 23);
+    assert!(!targets.contains(&at(5, 9)));
+    assert!(!targets.contains(&at(5, 12)));
+}
@@ -0,0 +1,81 @@
+/*
+Rules tests for how a match is won: the hunters bring the monster down, the monster outlasts every hunter or
+the round limit, or a team concedes. Nothing more can happen once there is a winner.
+*/
+
+mod common;
+
+use common::scenario::{hp, Scenario};
+
+use i_am_the_elder_god::gameplay_logic::entities::{ClassType, PlayerType, Status, BLEED_OUT_TURNS};
+use i_am_the_elder_god::gameplay_logic::game_match::Command;
+use i_am_the_elder_god::gameplay_logic::gameplay_type::Direction;
+use i_am_the_elder_god::gameplay_logic::turn_order::TurnScheme;
+
+#[test]
+fn hunters_win_when_the_monster_runs_out_of_hp() {
+    let mut game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 6).hp(1.0)
+        .entity(ClassType::Assault, 1, 5, 8)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Pierce"));
+
+    assert_eq!(game.winner(), PlayerType::Player2);
+    let wraith = game.entities()[0];
+    assert_eq!(hp(&wraith), 0.0);
+    assert_eq!(wraith.get_status().expect("Status"), Status::Normal, "The monster is never downed");
+    assert!(!game.apply(Command::EndTurn).expect("End turn"), "Nothing can be done once the match is won");
+}
+
+#[test]
+fn downed_hunters_keep_the_match_going_until_they_bleed_out() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::Initiative)
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Support, 1, 5, 4).hp(1.0)
+        .start();
+    assert!(game.apply(Command::Act(1, Direction::Up)).expect("Drain"));
+    assert_eq!(game.entities()[1].get_status().expect("Status"), Status::Downed);
+    assert_eq!(game.winner(), PlayerType::Undetermined);
+
+    //Each of the downed hunter's turns passes straight back to the monster
+    for turn in 1..=BLEED_OUT_TURNS {
+        assert_eq!(game.winner(), PlayerType::Undetermined, "Decided before turn {}", turn);
+        assert!(game.apply(Command::EndTurn).expect("End turn"));
+        assert_eq!(game.current(), 0);
+    }
+    assert_eq!(game.entities()[1].get_status().expect("Status"), Status::Dead);
+    assert_eq!(game.winner(), PlayerType::Player1);
+}
+
+#[test]
+fn monster_wins_by_outlasting_the_round_limit() {
+    let mut game = Scenario::open_field().scheme(TurnScheme::TeamPhase).round_limit(1)
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Support, 1, 9, 9)
+        .start();
+
+    assert!(game.apply(Command::EndTurn).expect("End turn"));
+    assert_eq!(game.winner(), PlayerType::Undetermined, "The monster still has its turn this round");
+    assert!(game.apply(Command::EndTurn).expect("End turn"));
+    assert_eq!(game.round(), 2);
+    assert_eq!(game.winner(), PlayerType::Player1);
+}
+
+#[test]
+fn conceding_hands_the_match_to_the_other_team() {
+    let scenario = || Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Support, 1, 9, 9);
+
+    let mut hunters_give_up = scenario().start();
+    assert!(hunters_give_up.apply(Command::Concede(PlayerType::Player2)).expect("Concede"));
+    assert_eq!(hunters_give_up.winner(), PlayerType::Player1);
+
+    let mut monster_gives_up = scenario().start();
+    assert!(monster_gives_up.apply(Command::Concede(PlayerType::Player1)).expect("Concede"));
+    assert_eq!(monster_gives_up.winner(), PlayerType::Player2);
+
+    let mut nobody = scenario().start();
+    assert!(!nobody.apply(Command::Concede(PlayerType::Undetermined)).expect("Concede"));
+    assert_eq!(nobody.winner(), PlayerType::Undetermined);
+}
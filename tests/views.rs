@@ -0,0 +1,59 @@
+/*
+Tests for the match as it is sent to another machine: each team is only sent what it can see, spectators only
+what both teams can, and nobody is sent the generator or seed that would let them foresee a roll.
+*/
+
+mod common;
+
+use common::scenario::{at, Scenario};
+
+use i_am_the_elder_god::gameplay_logic::entities::{ClassType, PlayerType, StatusEffect};
+use i_am_the_elder_god::gameplay_logic::game_match::MatchEvent;
+
+#[test]
+fn an_invisible_monster_is_only_sent_to_its_own_team() {
+    let game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 5).effect(StatusEffect::Invisible, 2)
+        .entity(ClassType::Assault, 1, 9, 9)
+        .start();
+
+    let position = |team| {
+        let (view, _) = game.view_for(team, &[]).expect("View");
+        (view.entities()[0].get_pos().expect("Monster"), view.entities()[1].get_pos().expect("Hunter"))
+    };
+    assert_eq!(position(Some(PlayerType::Player1)), (at(5, 5), at(9, 9)));
+    assert!(position(Some(PlayerType::Player2)).0.x < 0.0);
+    assert!(position(None).0.x < 0.0);
+    assert_eq!(position(None).1, at(9, 9));
+}
+
+#[test]
+fn what_happened_to_a_hidden_entity_is_left_out() {
+    let game = Scenario::open_field()
+        .entity(ClassType::Wraith, 1, 5, 5).effect(StatusEffect::Invisible, 2)
+        .entity(ClassType::Assault, 1, 9, 9)
+        .start();
+    let events = [
+        MatchEvent::Moved { entity: 0, from: at(5, 4), to: at(5, 5) },
+        MatchEvent::Moved { entity: 1, from: at(9, 8), to: at(9, 9) },
+        MatchEvent::TurnChanged { from: PlayerType::Player1, to: PlayerType::Player2 },
+    ];
+
+    let (_, seen) = game.view_for(Some(PlayerType::Player2), &events).expect("View");
+    assert_eq!(seen, events[1..].to_vec());
+    let (_, seen) = game.view_for(Some(PlayerType::Player1), &events).expect("View");
+    assert_eq!(seen, events.to_vec());
+}
+
+#[test]
+fn views_carry_nothing_to_foresee_rolls_with() {
+    let game = Scenario::open_field().seed(7)
+        .entity(ClassType::Wraith, 1, 5, 5)
+        .entity(ClassType::Assault, 1, 9, 9)
+        .start();
+
+    let (view, _) = game.view_for(Some(PlayerType::Player2), &[]).expect("View");
+    let json = serde_json::to_value(&view).expect("Json");
+    assert_eq!(json["rng"]["state"], 0);
+    assert_eq!(json["config"]["seed"], 0);
+}